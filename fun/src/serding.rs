@@ -1,13 +1,13 @@
 #![allow(dead_code)]
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use bevy_ecs::{error::Result, reflect::AppTypeRegistry};
 use bevy_log::*;
 use bevy_reflect::{
-	FromReflect, PartialReflect,
-	serde::{ReflectDeserializer, ReflectSerializer},
+	FromReflect, PartialReflect, TypeRegistry,
+	serde::{ReflectDeserializer, ReflectSerializer, TypedReflectSerializer},
 };
-use serde::de::DeserializeSeed;
+use serde::{Serialize, Serializer, de::DeserializeSeed, ser::SerializeMap};
 
 pub fn write_new_file(mut path: PathBuf, content: &[u8]) {
 	if let Err(err) = std::fs::write(&path, content) {
@@ -23,6 +23,47 @@ pub fn write_new_file(mut path: PathBuf, content: &[u8]) {
 	}
 }
 
+/// [write_new_file] with the directory created first, error returned.
+pub fn try_write_new_file(path: &Path, content: &[u8]) -> std::io::Result<()> {
+	if let Some(parent) = path.parent() {
+		std::fs::create_dir_all(parent)?;
+	}
+	std::fs::write(path, content)
+}
+
+/// Map of type path to value, what [ReflectSerializer] write for one value but for many.
+pub struct ReflectMapSerializer<'a>(pub &'a [Box<dyn PartialReflect>], pub &'a TypeRegistry);
+
+impl<'a> Serialize for ReflectMapSerializer<'a> {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		let mut map = serializer.serialize_map(Some(self.0.len()))?;
+		for value in self.0 {
+			let type_path = value
+				.get_represented_type_info()
+				.map(|type_info| type_info.type_path())
+				.unwrap_or_else(|| value.reflect_type_path());
+			map.serialize_entry(type_path, &TypedReflectSerializer::new(value.as_ref(), self.1))?;
+		}
+		map.end()
+	}
+}
+
+/// Name to [ReflectMapSerializer], in given order.
+struct ReflectMapsSerializer<'a>(Vec<(&'a str, &'a [Box<dyn PartialReflect>])>, &'a TypeRegistry);
+
+impl<'a> Serialize for ReflectMapsSerializer<'a> {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		serializer.collect_map(
+			self.0
+				.iter()
+				.map(|(name, values)| (name, ReflectMapSerializer(values, self.1))),
+		)
+	}
+}
+
+/// Name to reflected values, such as prototype components.
+pub type ReflectMaps<'a> = Vec<(&'a str, &'a [Box<dyn PartialReflect>])>;
+
 pub trait MoreReflect {
 	// RON FORMAT --------------------------------------------------------------------------------
 	fn save_assets_ron(&self, saving_settings: &dyn PartialReflect, path: PathBuf);
@@ -41,6 +82,14 @@ pub trait MoreReflect {
 	fn read_into_typed_bin<T: PartialReflect + FromReflect>(&self, path: PathBuf) -> Result<T>;
 	fn into_typed_bin<T: PartialReflect + FromReflect>(&self, data: &[u8]) -> Result<T>;
 	// BIN FORMAT --------------------------------------------------------------------------------
+
+	// REFLECT MAPS FORMAT -----------------------------------------------------------------------
+	/// Pretty, so diff line by line.
+	fn reflect_maps_ron(&self, maps: ReflectMaps) -> Result<String>;
+	fn reflect_maps_json(&self, maps: ReflectMaps) -> Result<String>;
+	fn save_reflect_maps_ron(&self, maps: ReflectMaps, path: PathBuf) -> Result;
+	fn save_reflect_maps_json(&self, maps: ReflectMaps, path: PathBuf) -> Result;
+	// REFLECT MAPS FORMAT -----------------------------------------------------------------------
 }
 
 impl MoreReflect for AppTypeRegistry {
@@ -145,4 +194,30 @@ impl MoreReflect for AppTypeRegistry {
 		reflected_type.ok_or("Unable to FromReflect".into())
 	}
 	// BIN FORMAT --------------------------------------------------------------------------------
+
+	// REFLECT MAPS FORMAT -----------------------------------------------------------------------
+	fn reflect_maps_ron(&self, maps: ReflectMaps) -> Result<String> {
+		let type_registry = self.read();
+		let serializer = ReflectMapsSerializer(maps, &type_registry);
+		Ok(ron::ser::to_string_pretty(&serializer, ron::ser::PrettyConfig::new())?)
+	}
+
+	fn reflect_maps_json(&self, maps: ReflectMaps) -> Result<String> {
+		let type_registry = self.read();
+		let serializer = ReflectMapsSerializer(maps, &type_registry);
+		Ok(serde_json::to_string_pretty(&serializer)?)
+	}
+
+	fn save_reflect_maps_ron(&self, maps: ReflectMaps, mut path: PathBuf) -> Result {
+		path.set_extension("ron");
+		try_write_new_file(&path, self.reflect_maps_ron(maps)?.as_bytes())?;
+		Ok(())
+	}
+
+	fn save_reflect_maps_json(&self, maps: ReflectMaps, mut path: PathBuf) -> Result {
+		path.set_extension("json");
+		try_write_new_file(&path, self.reflect_maps_json(maps)?.as_bytes())?;
+		Ok(())
+	}
+	// REFLECT MAPS FORMAT -----------------------------------------------------------------------
 }
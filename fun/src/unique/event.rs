@@ -0,0 +1,97 @@
+use bevy_ecs::prelude::*;
+use smol_str::SmolStr;
+
+use super::*;
+
+/// New name added to [UniqueHashed].
+#[derive(Event, Clone, Debug, PartialEq)]
+pub struct UniqueRegistered {
+	pub name: UniqueName,
+	pub entity: Entity,
+}
+
+/// Later duplicate merged into the [UniqueHashed] entity, contributor get despawned after.
+#[derive(Event, Clone, Debug, PartialEq)]
+pub struct UniqueOverridden {
+	pub name: UniqueName,
+	pub entity: Entity,
+	pub contributor: Entity,
+}
+
+/// Layer removed and prototype recomputed, see [UnloadMod].
+#[derive(Event, Clone, Debug, PartialEq)]
+pub struct UniqueRecomputed {
+	pub name: UniqueName,
+	pub entity: Entity,
+}
+
+/// Name removed from [UniqueHashed], entity already despawned when observed.
+#[derive(Event, Clone, Debug, PartialEq)]
+pub struct UniqueRemoved {
+	pub name: UniqueName,
+	pub entity: Entity,
+}
+
+/// Every collected prototype merged and validated, see [UniquePhase::Frozen].
+#[derive(Event, Clone, Debug, PartialEq)]
+pub struct UniqueFinalized;
+
+/// Mods moved to [FailedMods] by [UniqueSettings::auto_finalize] or late loading.
+#[derive(Event, Clone, Debug, PartialEq)]
+pub struct ModsFailed {
+	pub ids: Vec<SmolStr>,
+	pub error: LoadOrderError,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[derive(Resource, Default)]
+	struct Received(Vec<String>);
+
+	#[test]
+	fn test_events() {
+		let mut app = App::new();
+		app.add_plugins(UniquePlugin)
+			.init_resource::<Received>()
+			.add_observer(|event: On<UniqueRegistered>, mut received: ResMut<Received>| {
+				received.0.push(format!("Registered {}", event.name.0));
+			})
+			.add_observer(|event: On<UniqueOverridden>, mut received: ResMut<Received>| {
+				assert_ne!(event.entity, event.contributor);
+				received.0.push(format!("Overridden {}", event.name.0));
+			})
+			.add_observer(|event: On<UniqueRemoved>, mut received: ResMut<Received>| {
+				received.0.push(format!("Removed {}", event.name.0));
+			})
+			.add_systems(PreStartup, setup)
+			.add_systems(Startup, despawn)
+			.add_systems(Last, checkout);
+
+		app.run();
+	}
+
+	fn setup(world: &mut World) {
+		world.spawn(UniqueName::new("Test_1"));
+		world.spawn(UniqueName::new("Test_1"));
+		world.spawn(UniqueName::new("Test_2"));
+	}
+
+	fn despawn(world: &mut World) {
+		let ent = world.resource::<UniqueHashed>().get_ent("Test_2").unwrap();
+		world.despawn(ent);
+	}
+
+	fn checkout(received: Res<Received>) {
+		assert_eq!(
+			received.0,
+			[
+				"Registered Test_1",
+				"Overridden Test_1",
+				"Registered Test_2",
+				"Removed Test_2"
+			]
+		);
+	}
+}
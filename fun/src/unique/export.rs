@@ -0,0 +1,182 @@
+use std::{collections::BTreeMap, path::PathBuf};
+
+use bevy_ecs::prelude::*;
+use bevy_reflect::{PartialReflect, TypeRegistry};
+
+use super::*;
+use crate::serding::{MoreReflect, ReflectMaps};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+	Ron,
+	Json,
+}
+
+impl ExportFormat {
+	pub fn extension(self) -> &'static str {
+		match self {
+			Self::Ron => "ron",
+			Self::Json => "json",
+		}
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportLayout {
+	/// Every prototype in one file, path is the file.
+	Bundle,
+	/// One file per prototype named after it, path is the directory.
+	PerPrototype,
+}
+
+/// Merged prototype of every [UniqueHashed] name, sorted by name so export diff cleanly.
+pub fn merged_prototypes(world: &World, registry: &TypeRegistry) -> BTreeMap<UniqueName, Vec<Box<dyn PartialReflect>>> {
+	world
+		.resource::<UniqueHashed>()
+		.iter()
+		.map(|(name, entity)| {
+			let mut components = snapshot_components(world, registry, *entity);
+			components.sort_by_key(|component| {
+				component
+					.get_represented_type_info()
+					.map(|type_info| type_info.type_path())
+			});
+			(name.clone(), components)
+		})
+		.collect()
+}
+
+/// Same format as prototype file, read back with [read_prototypes].
+pub fn prototypes_to_string(world: &World, format: ExportFormat) -> Result<String> {
+	let app_registry = world.resource::<AppTypeRegistry>();
+	let prototypes = merged_prototypes(world, &app_registry.read());
+	let maps = reflect_maps(&prototypes);
+	match format {
+		ExportFormat::Ron => app_registry.reflect_maps_ron(maps),
+		ExportFormat::Json => app_registry.reflect_maps_json(maps),
+	}
+}
+
+/// Dump what actually won the merge through [MoreReflect].
+///
+/// Per prototype file named after it with namespace and path separator as `_`,
+/// error before writing anything if two names give the same file.
+pub fn export_prototypes(world: &World, mut path: PathBuf, format: ExportFormat, layout: ExportLayout) -> Result {
+	let app_registry = world.resource::<AppTypeRegistry>();
+	let prototypes = merged_prototypes(world, &app_registry.read());
+	let save = |maps: ReflectMaps, path: PathBuf| match format {
+		ExportFormat::Ron => app_registry.save_reflect_maps_ron(maps, path),
+		ExportFormat::Json => app_registry.save_reflect_maps_json(maps, path),
+	};
+
+	match layout {
+		ExportLayout::Bundle => save(reflect_maps(&prototypes), path),
+		ExportLayout::PerPrototype => {
+			let mut files = BTreeMap::<PathBuf, &UniqueName>::new();
+			for name in prototypes.keys() {
+				let mut file = path.clone();
+				file.push(name.0.replace([NAMESPACE_SEPARATOR, '/', '\\', '.'], "_"));
+				file.set_extension(format.extension());
+				if let Some(other) = files.insert(file.clone(), name) {
+					return Err(
+						format!("Prototype {} and {} both export to {}", other.0, name.0, file.display()).into(),
+					);
+				}
+			}
+
+			std::fs::create_dir_all(&path)?;
+			for (file, name) in files {
+				save(vec![(name.0.as_str(), prototypes[name].as_slice())], file)?;
+			}
+			Ok(())
+		}
+	}
+}
+
+fn reflect_maps(prototypes: &BTreeMap<UniqueName, Vec<Box<dyn PartialReflect>>>) -> ReflectMaps<'_> {
+	prototypes
+		.iter()
+		.map(|(name, components)| (name.0.as_str(), components.as_slice()))
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use bevy_reflect::Reflect;
+
+	#[derive(Component, Reflect, Default)]
+	#[reflect(Component)]
+	struct Damage(u32);
+
+	#[derive(Component, Reflect, Default)]
+	#[reflect(Component)]
+	struct Weight(u32);
+
+	#[test]
+	fn test_export() {
+		let mut app = App::new();
+		app.add_plugins(UniquePlugin)
+			.register_type::<Damage>()
+			.register_type::<Weight>()
+			.add_systems(Startup, setup)
+			.add_systems(Last, checkout);
+
+		app.run();
+	}
+
+	fn setup(world: &mut World) {
+		world.spawn((Damage(10), Weight(3), UniqueName::new("core:sword")));
+		world.spawn((Damage(25), UniqueName::new("core:sword")));
+		world.spawn((Weight(8), UniqueName::new("core:shield")));
+	}
+
+	fn checkout(world: &mut World) {
+		let exported = prototypes_to_string(world, ExportFormat::Ron).unwrap();
+		let registry = world.resource::<AppTypeRegistry>().read();
+		let prototypes = read_prototypes(exported.as_bytes(), &registry).unwrap();
+
+		let names = prototypes
+			.iter()
+			.map(|prototype| prototype.name.0.as_str())
+			.collect::<Vec<_>>();
+		assert_eq!(names, ["core:shield", "core:sword"]);
+		let damage = prototypes[1].components[0].reflect_ref().as_tuple_struct().unwrap();
+		assert_eq!(damage.field(0).unwrap().try_downcast_ref::<u32>(), Some(&25));
+		drop(registry);
+
+		let json = prototypes_to_string(world, ExportFormat::Json).unwrap();
+		assert!(json.contains("core:shield"));
+
+		let mut dir = std::env::temp_dir();
+		dir.push(format!("unique_export_test_{}", std::process::id()));
+		let mut bundle = dir.clone();
+		bundle.push("bundle");
+		bundle.push("prototypes");
+		export_prototypes(world, bundle.clone(), ExportFormat::Ron, ExportLayout::Bundle).unwrap();
+		bundle.set_extension("ron");
+		let registry = world.resource::<AppTypeRegistry>().read();
+		let prototypes = read_prototypes(&std::fs::read(bundle).unwrap(), &registry).unwrap();
+		assert_eq!(prototypes.len(), 2);
+		drop(registry);
+
+		let mut per_prototype = dir.clone();
+		per_prototype.push("per_prototype");
+		export_prototypes(
+			world,
+			per_prototype.clone(),
+			ExportFormat::Ron,
+			ExportLayout::PerPrototype,
+		)
+		.unwrap();
+		let mut file = per_prototype.clone();
+		file.push("core_sword.ron");
+		assert!(file.exists());
+
+		// Same file as core:sword
+		world.spawn((Weight(1), UniqueName::new("core/sword")));
+		let collided = export_prototypes(world, per_prototype, ExportFormat::Ron, ExportLayout::PerPrototype);
+		std::fs::remove_dir_all(dir).unwrap();
+		assert!(collided.is_err());
+	}
+}
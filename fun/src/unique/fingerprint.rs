@@ -0,0 +1,211 @@
+use std::collections::BTreeMap;
+
+use bevy_derive::*;
+use bevy_ecs::prelude::*;
+use bevy_log::prelude::*;
+use bevy_reflect::{PartialReflect, Reflect, TypeRegistry};
+use serde::{Deserialize, Serialize};
+
+use super::*;
+use crate::serding::ReflectMapSerializer;
+
+/// Content hash of the prototype reflected components, set on [UniquePhase::Frozen].
+///
+/// Hash of the serialized components sorted by type path, same content give same hash every run.
+#[derive(Component, Reflect, Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[reflect(Component)]
+pub struct UniqueFingerprint(pub u64);
+
+impl UniqueFingerprint {
+	pub fn of(mut components: Vec<Box<dyn PartialReflect>>, registry: &TypeRegistry) -> Result<Self> {
+		components.sort_by_key(|component| {
+			component
+				.get_represented_type_info()
+				.map(|type_info| type_info.type_path())
+		});
+		let content = ron::ser::to_string(&ReflectMapSerializer(&components, registry))?;
+		Ok(Self(fnv1a(content.as_bytes())))
+	}
+}
+
+/// Set [UniqueFingerprint] on every [UniqueHashed] entity.
+pub fn fingerprint_prototypes(world: &mut World) {
+	let registry = world.resource::<AppTypeRegistry>().clone();
+	let registry = registry.read();
+	let prototypes = world
+		.resource::<UniqueHashed>()
+		.iter()
+		.map(|(name, entity)| (name.clone(), *entity))
+		.collect::<Vec<_>>();
+
+	for (name, entity) in prototypes {
+		let components = snapshot_components(world, &registry, entity);
+		match UniqueFingerprint::of(components, &registry) {
+			Ok(fingerprint) => {
+				world.entity_mut(entity).insert(fingerprint);
+			}
+			Err(err) => warn!("Fail to fingerprint {}: {}", name.0, err),
+		}
+	}
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FingerprintStatus {
+	Unchanged,
+	Changed,
+	Missing,
+}
+
+/// Fingerprint of referenced prototype by [UniqueId], saved alongside the [UniqueRef].
+#[derive(Reflect, Serialize, Deserialize, Clone, Debug, Default, PartialEq, Deref)]
+pub struct SavedFingerprints(pub BTreeMap<UniqueId, UniqueFingerprint>);
+
+impl SavedFingerprints {
+	/// Id without [UniqueFingerprint] skipped.
+	pub fn capture(world: &World, ids: impl IntoIterator<Item = UniqueId>) -> Self {
+		let hashed = world.resource::<UniqueHashed>();
+		Self(
+			ids.into_iter()
+				.filter_map(|id| {
+					let fingerprint = world.get::<UniqueFingerprint>(hashed.get_ent_by_id(id)?)?;
+					Some((id, *fingerprint))
+				})
+				.collect(),
+		)
+	}
+
+	pub fn check(&self, world: &World) -> FingerprintReport {
+		let hashed = world.resource::<UniqueHashed>();
+		let mut report = FingerprintReport::default();
+		for (id, saved) in self.iter() {
+			let current = hashed
+				.get_ent_by_id(*id)
+				.and_then(|entity| world.get::<UniqueFingerprint>(entity));
+			let ids = match compare(current, *saved) {
+				FingerprintStatus::Unchanged => &mut report.unchanged,
+				FingerprintStatus::Changed => &mut report.changed,
+				FingerprintStatus::Missing => &mut report.missing,
+			};
+			ids.push(*id);
+		}
+		report
+	}
+}
+
+pub fn fingerprint_status(world: &World, name: &str, saved: UniqueFingerprint) -> FingerprintStatus {
+	let current = world
+		.resource::<UniqueHashed>()
+		.get_ent(name)
+		.and_then(|entity| world.get::<UniqueFingerprint>(entity));
+	compare(current, saved)
+}
+
+fn compare(current: Option<&UniqueFingerprint>, saved: UniqueFingerprint) -> FingerprintStatus {
+	match current {
+		None => FingerprintStatus::Missing,
+		Some(current) if *current == saved => FingerprintStatus::Unchanged,
+		Some(_) => FingerprintStatus::Changed,
+	}
+}
+
+/// Referenced ids by [FingerprintStatus], in id order.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FingerprintReport {
+	pub unchanged: Vec<UniqueId>,
+	pub changed: Vec<UniqueId>,
+	pub missing: Vec<UniqueId>,
+}
+
+impl FingerprintReport {
+	pub fn is_compatible(&self) -> bool {
+		self.changed.is_empty() && self.missing.is_empty()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use smol_str::SmolStr;
+
+	#[derive(Component, Reflect, Default)]
+	#[reflect(Component)]
+	struct Damage(u32);
+
+	#[derive(Component, Reflect, Default)]
+	#[reflect(Component)]
+	struct Weight(u32);
+
+	#[derive(Resource)]
+	struct Saved(SavedFingerprints);
+
+	#[test]
+	fn test_fingerprint() {
+		let mut app = App::new();
+		app.add_plugins(UniquePlugin)
+			.register_type::<Damage>()
+			.register_type::<Weight>()
+			.add_systems(Startup, setup)
+			.add_systems(PreUpdate, save.after(auto_finalize))
+			.add_systems(Update, change)
+			.add_systems(Last, checkout);
+
+		app.run();
+	}
+
+	fn source(mod_id: &str) -> PrototypeSource {
+		PrototypeSource {
+			mod_id: SmolStr::new(mod_id),
+			file: SmolStr::default(),
+		}
+	}
+
+	fn setup(world: &mut World) {
+		world.spawn((source("base"), Damage(10), Weight(3), UniqueName::new("Sword")));
+		world.spawn((source("base"), Weight(3), Damage(10), UniqueName::new("Club")));
+		world.spawn((source("base"), Weight(8), UniqueName::new("Shield")));
+		world.spawn((source("patch"), Damage(25), UniqueName::new("Sword")));
+		world.spawn((source("patch"), Weight(1), UniqueName::new("Dagger")));
+	}
+
+	fn save(world: &mut World) {
+		let hashed = world.resource::<UniqueHashed>();
+		let sword = hashed.get_ent("Sword").unwrap();
+		let club = hashed.get_ent("Club").unwrap();
+		assert_ne!(
+			world.get::<UniqueFingerprint>(sword),
+			world.get::<UniqueFingerprint>(club)
+		);
+
+		let ids = ["Sword", "Club", "Shield", "Dagger"].map(|name| hashed.get_id(name).unwrap());
+		let saved = SavedFingerprints::capture(world, ids);
+		assert_eq!(saved.len(), 4);
+		world.insert_resource(Saved(saved));
+	}
+
+	fn change(mut cmd: Commands) {
+		cmd.queue(UnloadMod(SmolStr::new("patch")));
+	}
+
+	fn checkout(world: &mut World) {
+		let report = world.resource::<Saved>().0.check(world);
+		assert!(!report.is_compatible());
+		let mut unchanged = [UniqueId::of("Club"), UniqueId::of("Shield")];
+		unchanged.sort();
+		assert_eq!(report.unchanged, unchanged);
+		assert_eq!(report.changed, [UniqueId::of("Sword")]);
+		assert_eq!(report.missing, [UniqueId::of("Dagger")]);
+
+		// Saved by id, not by name
+		let saved = ron::ser::to_string(&world.resource::<Saved>().0).unwrap();
+		assert!(!saved.contains("Sword"));
+
+		// Same content as Club now
+		let hashed = world.resource::<UniqueHashed>();
+		let sword = hashed.get_ent("Sword").unwrap();
+		let club = hashed.get_ent("Club").unwrap();
+		assert_eq!(
+			world.get::<UniqueFingerprint>(sword),
+			world.get::<UniqueFingerprint>(club)
+		);
+	}
+}
@@ -0,0 +1,111 @@
+use bevy_ecs::prelude::*;
+use bevy_log::prelude::*;
+use bevy_reflect::Reflect;
+use serde::{Deserialize, Serialize};
+
+use super::*;
+
+/// Compact form of [UniqueName], FNV-1a hash of the name so same name get same id every run.
+///
+/// Collided name get the next free id with a warning, only stable as long as load order is.
+#[derive(Reflect, Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct UniqueId(pub u64);
+
+impl UniqueId {
+	pub fn of(name: &str) -> Self {
+		Self(fnv1a(name.as_bytes()))
+	}
+}
+
+/// Stable on every platform and version, unlike [std::hash::DefaultHasher].
+pub(super) fn fnv1a(bytes: &[u8]) -> u64 {
+	bytes.iter().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+		(hash ^ *byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+	})
+}
+
+/// Saved reference to a prototype, resolve with [UniqueHashed::get_ent_by_id].
+#[derive(Component, Reflect, Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[reflect(Component)]
+pub struct UniqueRef(pub UniqueId);
+
+impl UniqueHashed {
+	pub fn get_id(&self, name: &str) -> Option<UniqueId> {
+		self.ids.get(&UniqueName::new(name)).cloned()
+	}
+
+	pub fn get_name(&self, id: UniqueId) -> Option<&UniqueName> {
+		self.names.get(&id)
+	}
+
+	pub fn get_ent_by_id(&self, id: UniqueId) -> Option<Entity> {
+		self.get(self.get_name(id)?).cloned()
+	}
+
+	pub fn get_ref(&self, name: &str) -> Option<UniqueRef> {
+		self.get_id(name).map(UniqueRef)
+	}
+
+	pub(super) fn register(&mut self, name: UniqueName, entity: Entity) -> UniqueId {
+		let mut id = UniqueId::of(&name.0);
+		while let Some(collided) = self.names.get(&id) {
+			warn!("Unique id collision between {} and {}", collided.0, name.0);
+			id.0 = id.0.wrapping_add(1);
+		}
+		self.names.insert(id, name.clone());
+		self.ids.insert(name.clone(), id);
+		self.entities.insert(name, entity);
+		id
+	}
+
+	pub(super) fn unregister(&mut self, name: &UniqueName) {
+		self.entities.remove(name);
+		if let Some(id) = self.ids.remove(name) {
+			self.names.remove(&id);
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::serding::MoreReflect;
+
+	#[test]
+	fn test_unique_id() {
+		let mut app = App::new();
+		app.add_plugins(UniquePlugin)
+			.add_systems(Startup, setup)
+			.add_systems(Last, checkout);
+
+		app.run();
+	}
+
+	fn setup(world: &mut World) {
+		world.spawn(UniqueName::new("Sword"));
+		world.spawn(UniqueName::new("Shield"));
+
+		// Occupied id get the next one
+		let mut hashed = world.resource_mut::<UniqueHashed>();
+		let id = UniqueId::of("Collided");
+		hashed.names.insert(id, UniqueName::new("Taken"));
+		let collided = hashed.register(UniqueName::new("Collided"), Entity::PLACEHOLDER);
+		assert_eq!(collided, UniqueId(id.0 + 1));
+		hashed.unregister(&UniqueName::new("Collided"));
+		hashed.names.remove(&id);
+	}
+
+	fn checkout(world: &mut World) {
+		let hashed = world.resource::<UniqueHashed>();
+		let sword = hashed.get_ent("Sword").unwrap();
+		let id = hashed.get_id("Sword").unwrap();
+		assert_eq!(id, UniqueId::of("Sword"));
+		assert_eq!(hashed.get_name(id), Some(&UniqueName::new("Sword")));
+		assert_eq!(hashed.get_ent_by_id(id), Some(sword));
+
+		let saved = format!(r#"{{ "reflection_fun::unique::id::UniqueRef": (({})) }}"#, id.0);
+		let registry = world.resource::<AppTypeRegistry>();
+		let loaded = registry.into_typed_ron::<UniqueRef>(saved.as_bytes()).unwrap();
+		assert_eq!(loaded, hashed.get_ref("Sword").unwrap());
+	}
+}
@@ -0,0 +1,229 @@
+use std::any::TypeId;
+
+use bevy_derive::*;
+use bevy_ecs::prelude::*;
+use bevy_log::prelude::*;
+use bevy_platform::collections::HashMap;
+use bevy_reflect::PartialReflect;
+use smol_str::SmolStr;
+
+use super::*;
+
+/// Instance spawned from the [UniqueHashed] entity.
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+#[relationship(relationship_target = PrototypeInstances)]
+pub struct InstanceOf(pub Entity);
+
+/// On the [UniqueHashed] entity, every live instance.
+#[derive(Component, Default, Debug)]
+#[relationship_target(relationship = InstanceOf)]
+pub struct PrototypeInstances(Vec<Entity>);
+
+/// Value last received from the prototype per component.
+///
+/// Instance with it receive prototype change, unless the component differ from this value (modified locally).
+#[derive(Component, Default, Debug, Deref)]
+pub struct PrototypeSync(HashMap<TypeId, Box<dyn PartialReflect>>);
+
+/// Copy every reflected component of the prototype into the entity.
+pub struct InstanceFrom {
+	pub name: UniqueName,
+	/// Insert [PrototypeSync] so prototype change are received.
+	pub sync: bool,
+	/// Substituted into [PrototypeParams], kept as [PrototypeArgs] if not empty.
+	pub args: PrototypeArgs,
+}
+
+impl InstanceFrom {
+	pub fn new(name: &str, sync: bool) -> Self {
+		Self {
+			name: UniqueName::new(name),
+			sync,
+			args: PrototypeArgs::default(),
+		}
+	}
+
+	pub fn with_arg(mut self, name: &str, value: ParamValue) -> Self {
+		self.args.insert(SmolStr::new(name), value);
+		self
+	}
+}
+
+impl EntityCommand for InstanceFrom {
+	fn apply(self, mut ent_mut: EntityWorldMut) {
+		let entity = ent_mut.id();
+		ent_mut.world_scope(|world| {
+			let Some(prototype) = world.resource::<UniqueHashed>().get(&self.name).cloned() else {
+				warn!("Instance from unknown prototype: {}", self.name.0);
+				return;
+			};
+
+			let registry = world.resource::<AppTypeRegistry>().clone();
+			let registry = registry.read();
+			let components = instance_components(world, &registry, prototype, &self.args);
+
+			let mut ent_mut = world.entity_mut(entity);
+			insert_layer(&mut ent_mut, &registry, &components);
+			ent_mut.insert(InstanceOf(prototype));
+			if !self.args.is_empty() {
+				ent_mut.insert(self.args);
+			}
+			if self.sync {
+				let synced = components
+					.into_iter()
+					.filter_map(|component| Some((component.get_represented_type_info()?.type_id(), component)))
+					.collect();
+				ent_mut.insert(PrototypeSync(synced));
+			}
+		});
+	}
+}
+
+/// Apply prototype change to every instance with [PrototypeSync] if not modified locally.
+pub struct PropagatePrototype(pub Entity);
+
+impl Command for PropagatePrototype {
+	fn apply(self, world: &mut World) {
+		propagate_to_instances(world, self.0);
+	}
+}
+
+pub(super) fn propagate_to_instances(world: &mut World, prototype: Entity) {
+	let Some(instances) = world
+		.get::<PrototypeInstances>(prototype)
+		.map(|instances| instances.0.clone())
+	else {
+		return;
+	};
+
+	let registry = world.resource::<AppTypeRegistry>().clone();
+	let registry = registry.read();
+	for instance in instances {
+		let Some(mut sync) = world.entity_mut(instance).take::<PrototypeSync>() else {
+			continue;
+		};
+		let args = world.get::<PrototypeArgs>(instance).cloned().unwrap_or_default();
+		let components = instance_components(world, &registry, prototype, &args)
+			.into_iter()
+			.filter_map(|component| Some((component.get_represented_type_info()?.type_id(), component)))
+			.collect::<HashMap<_, _>>();
+
+		let mut ent_mut = world.entity_mut(instance);
+		let type_ids = components.keys().chain(sync.keys()).cloned().collect::<Vec<_>>();
+		for type_id in type_ids {
+			let Some(reflect_component) = registry.get_type_data::<ReflectComponent>(type_id) else {
+				continue;
+			};
+			let current = reflect_component.reflect(ent_mut.as_readonly());
+			let is_local = match (current, sync.0.get(&type_id)) {
+				(Some(current), Some(received)) => current.reflect_partial_eq(received.as_ref()) != Some(true),
+				// Removed locally or added locally
+				(None, Some(_)) | (Some(_), None) => true,
+				(None, None) => false,
+			};
+			if is_local {
+				continue;
+			}
+
+			match components.get(&type_id) {
+				Some(component) => {
+					reflect_component.insert(&mut ent_mut, component.as_ref(), &registry);
+					sync.0.insert(type_id, component.to_dynamic());
+				}
+				None => {
+					reflect_component.remove(&mut ent_mut);
+					sync.0.remove(&type_id);
+				}
+			}
+		}
+		ent_mut.insert(sync);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use bevy_reflect::Reflect;
+
+	#[derive(Component, Reflect, Default)]
+	#[reflect(Component)]
+	struct Damage(u32);
+
+	#[derive(Component, Reflect, Default)]
+	#[reflect(Component)]
+	struct Weight(u32);
+
+	#[derive(Resource)]
+	struct Instances {
+		synced: Entity,
+		modified: Entity,
+		unsynced: Entity,
+	}
+
+	#[test]
+	fn test_propagate() {
+		let mut app = App::new();
+		app.add_plugins(UniquePlugin)
+			.register_type::<Damage>()
+			.register_type::<Weight>()
+			.add_systems(PreStartup, setup)
+			.add_systems(Startup, spawn_instances)
+			.add_systems(PostStartup, patch)
+			.add_systems(PreUpdate, check_patched)
+			.add_systems(Last, checkout);
+
+		app.run();
+	}
+
+	fn source(mod_id: &str) -> PrototypeSource {
+		PrototypeSource {
+			mod_id: SmolStr::new(mod_id),
+			file: SmolStr::default(),
+		}
+	}
+
+	fn setup(world: &mut World) {
+		world.spawn((source("base"), Damage(10), Weight(3), UniqueName::new("Sword")));
+	}
+
+	fn spawn_instances(mut cmd: Commands) {
+		let synced = cmd.spawn_empty().queue(InstanceFrom::new("Sword", true)).id();
+		let modified = cmd.spawn_empty().queue(InstanceFrom::new("Sword", true)).id();
+		let unsynced = cmd.spawn_empty().queue(InstanceFrom::new("Sword", false)).id();
+		cmd.entity(modified).insert(Damage(99));
+		cmd.insert_resource(Instances {
+			synced,
+			modified,
+			unsynced,
+		});
+	}
+
+	fn patch(world: &mut World) {
+		world.spawn((source("patch"), Damage(25), Weight(4), UniqueName::new("Sword")));
+	}
+
+	fn check_patched(world: &mut World) {
+		let instances = world.resource::<Instances>();
+		let (synced, modified, unsynced) = (instances.synced, instances.modified, instances.unsynced);
+		let sword = world.resource::<UniqueHashed>().get_ent("Sword").unwrap();
+		assert_eq!(world.get::<InstanceOf>(synced), Some(&InstanceOf(sword)));
+
+		assert_eq!(world.get::<Damage>(synced).unwrap().0, 25);
+		assert_eq!(world.get::<Weight>(synced).unwrap().0, 4);
+		assert_eq!(world.get::<Damage>(modified).unwrap().0, 99);
+		assert_eq!(world.get::<Weight>(modified).unwrap().0, 4);
+		assert_eq!(world.get::<Damage>(unsynced).unwrap().0, 10);
+
+		world.commands().queue(UnloadMod(SmolStr::new("patch")));
+	}
+
+	fn checkout(world: &mut World) {
+		let instances = world.resource::<Instances>();
+		let (synced, modified) = (instances.synced, instances.modified);
+
+		assert_eq!(world.get::<Damage>(synced).unwrap().0, 10);
+		assert_eq!(world.get::<Weight>(synced).unwrap().0, 3);
+		assert_eq!(world.get::<Damage>(modified).unwrap().0, 99);
+		assert_eq!(world.get::<Weight>(modified).unwrap().0, 3);
+	}
+}
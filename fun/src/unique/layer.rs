@@ -0,0 +1,262 @@
+use std::any::TypeId;
+
+use bevy_derive::*;
+use bevy_ecs::{entity_disabling::Disabled, prelude::*};
+use bevy_log::prelude::*;
+use bevy_reflect::{PartialReflect, TypeRegistry};
+use smol_str::SmolStr;
+
+use super::*;
+
+/// Reflected components of one contributing entity, captured before merge.
+#[derive(Debug)]
+pub struct PrototypeLayer {
+	pub source: PrototypeSource,
+	pub components: Vec<Box<dyn PartialReflect>>,
+	/// [UniqueReport] source label and every contributed component name, reflected or not.
+	pub contributed: (SmolStr, Vec<SmolStr>),
+}
+
+impl PrototypeLayer {
+	/// Same record for first merge and [recompute_prototype].
+	fn contribute(&self, report: &mut UniqueReport, name: &UniqueName) {
+		let (source, component_names) = &self.contributed;
+		report.contribute(name, source, component_names.clone());
+	}
+}
+
+/// Every layer of the [UniqueHashed] entity, lowest first. Effective components computed from it.
+///
+/// Only components registered with [ReflectComponent] are layered,
+/// others are still overwritten and stay after their layer is removed.
+#[derive(Component, Default, Debug, Deref)]
+pub struct PrototypeLayers(Vec<PrototypeLayer>);
+
+impl PrototypeLayers {
+	fn type_ids(&self) -> Vec<TypeId> {
+		let mut type_ids = self
+			.iter()
+			.flat_map(|layer| &layer.components)
+			.filter_map(|component| component.get_represented_type_info())
+			.map(|type_info| type_info.type_id())
+			.collect::<Vec<_>>();
+		type_ids.sort();
+		type_ids.dedup();
+		type_ids
+	}
+}
+
+/// Snapshot `layer` and push it on top of `entity` layers, also applied if not the same entity.
+///
+/// Contribution recorded in [UniqueReport].
+pub(super) fn push_layer(world: &mut World, entity: Entity, layer: Entity) {
+	let registry = world.resource::<AppTypeRegistry>().clone();
+	let registry = registry.read();
+	let pushed = PrototypeLayer {
+		source: world.get::<PrototypeSource>(layer).cloned().unwrap_or_default(),
+		components: snapshot_components(world, &registry, layer),
+		contributed: contribution(world.entity(layer), world.components()),
+	};
+	if let Some(name) = world.get::<UniqueName>(entity).cloned() {
+		pushed.contribute(&mut world.resource_mut::<UniqueReport>(), &name);
+	}
+
+	let mut ent_mut = world.entity_mut(entity);
+	if entity != layer {
+		insert_layer(&mut ent_mut, &registry, &pushed.components);
+		ent_mut.insert(pushed.source.clone());
+	}
+	match ent_mut.get_mut::<PrototypeLayers>() {
+		Some(mut layers) => layers.0.push(pushed),
+		None => {
+			ent_mut.insert(PrototypeLayers(vec![pushed]));
+		}
+	}
+}
+
+/// Remove every layered component then insert again from lowest layer,
+/// despawn when no layer left so it is removed from [UniqueHashed].
+pub fn recompute_prototype(world: &mut World, entity: Entity, previous: &[TypeId]) {
+	let Some(layers) = world.entity_mut(entity).take::<PrototypeLayers>() else {
+		warn!("Recompute prototype without layers: {}", entity);
+		return;
+	};
+	if layers.is_empty() {
+		world.despawn(entity);
+		return;
+	}
+
+	let registry = world.resource::<AppTypeRegistry>().clone();
+	let registry = registry.read();
+	let name = world.entity(entity).get::<UniqueName>().cloned();
+	let mut ent_mut = world.entity_mut(entity);
+	for type_id in previous {
+		if let Some(reflect_component) = registry.get_type_data::<ReflectComponent>(*type_id) {
+			reflect_component.remove(&mut ent_mut);
+		}
+	}
+
+	for layer in layers.iter() {
+		insert_layer(&mut ent_mut, &registry, &layer.components);
+	}
+	if let Some(name) = &name {
+		let mut unique_report = world.resource_mut::<UniqueReport>();
+		unique_report.remove(name);
+		for layer in layers.iter() {
+			layer.contribute(&mut unique_report, name);
+		}
+	}
+	world
+		.entity_mut(entity)
+		.insert((layers.last().unwrap().source.clone(), layers));
+	propagate_to_instances(world, entity);
+
+	if let Some(name) = name {
+		world.trigger(UniqueRecomputed { name, entity });
+	}
+}
+
+/// Every reflected component not used by unique itself.
+pub(super) fn snapshot_components(
+	world: &World,
+	registry: &TypeRegistry,
+	entity: Entity,
+) -> Vec<Box<dyn PartialReflect>> {
+	let ent_ref = world.entity(entity);
+	ent_ref
+		.archetype()
+		.iter_components()
+		.filter_map(|component_id| world.components().get_info(component_id)?.type_id())
+		.filter(|type_id| !is_unique_internal(*type_id))
+		.filter_map(|type_id| registry.get_type_data::<ReflectComponent>(type_id))
+		.filter_map(|reflect_component| reflect_component.reflect(ent_ref))
+		.map(|reflected| reflected.to_dynamic())
+		.collect()
+}
+
+/// Insert reflected components, return type path of inserted one.
+pub(super) fn insert_layer(
+	ent_mut: &mut EntityWorldMut,
+	registry: &TypeRegistry,
+	components: &[Box<dyn PartialReflect>],
+) -> Vec<SmolStr> {
+	let mut component_names = Vec::with_capacity(components.len());
+	for component in components {
+		let Some(type_info) = component.get_represented_type_info() else {
+			continue;
+		};
+		if let Some(reflect_component) = registry.get_type_data::<ReflectComponent>(type_info.type_id()) {
+			reflect_component.insert(ent_mut, component.as_ref(), registry);
+			component_names.push(SmolStr::new(type_info.type_path()));
+		}
+	}
+	component_names
+}
+
+/// Remove every layer from the mod, then recompute affected prototypes.
+pub struct UnloadMod(pub SmolStr);
+
+impl Command for UnloadMod {
+	fn apply(self, world: &mut World) {
+		let mut q_layers = world.query_filtered::<(Entity, &mut PrototypeLayers), With<Disabled>>();
+		let changed = q_layers
+			.iter_mut(world)
+			.filter_map(|(entity, mut layers)| {
+				let previous = layers.type_ids();
+				let before = layers.len();
+				layers.0.retain(|layer| layer.source.mod_id != self.0);
+				(before != layers.len()).then_some((entity, previous))
+			})
+			.collect::<Vec<_>>();
+
+		let any_changed = !changed.is_empty();
+		for (entity, previous) in changed {
+			recompute_prototype(world, entity, &previous);
+		}
+		if any_changed {
+			fingerprint_prototypes(world);
+		}
+
+		world.resource_mut::<ModLoadOrder>().0.retain(|id| *id != self.0);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use bevy_reflect::Reflect;
+	use bevy_utils::prelude::DebugName;
+	use smol_str::ToSmolStr;
+
+	#[derive(Component, Reflect, Default)]
+	#[reflect(Component)]
+	struct Damage(u32);
+
+	/// Not reflected, never layered but still in [UniqueReport]
+	#[derive(Component)]
+	struct Sharp;
+
+	#[derive(Component, Reflect, Default)]
+	#[reflect(Component)]
+	struct Weight(u32);
+
+	#[test]
+	fn test_unload_mod() {
+		let mut app = App::new();
+		app.add_plugins(UniquePlugin)
+			.register_type::<Damage>()
+			.register_type::<Weight>()
+			.add_systems(PreStartup, setup)
+			.add_systems(Startup, unload)
+			.add_systems(Last, checkout);
+
+		app.run();
+	}
+
+	fn setup(world: &mut World) {
+		let source = |mod_id: &str| PrototypeSource {
+			mod_id: SmolStr::new(mod_id),
+			file: SmolStr::default(),
+		};
+		world.spawn((source("base"), Damage(10), Weight(3), Sharp, UniqueName::new("Sword")));
+		world.spawn((source("patch"), Damage(25), UniqueName::new("Sword")));
+		world.spawn((source("extra"), Weight(1), UniqueName::new("Sword")));
+		world.spawn((source("patch"), Damage(5), UniqueName::new("Dagger")));
+	}
+
+	fn unload(world: &mut World) {
+		let hashed = world.resource::<UniqueHashed>();
+		let sword = hashed.get_ent("Sword").unwrap();
+		assert_eq!(world.get::<Damage>(sword).unwrap().0, 25);
+		assert_eq!(world.get::<Weight>(sword).unwrap().0, 1);
+		assert_eq!(world.get::<PrototypeLayers>(sword).unwrap().len(), 3);
+
+		world.commands().queue(UnloadMod(SmolStr::new("patch")));
+	}
+
+	fn checkout(world: &mut World) {
+		let hashed = world.resource::<UniqueHashed>();
+		assert!(hashed.get_ent("Dagger").is_none());
+		let sword = hashed.get_ent("Sword").unwrap();
+
+		assert_eq!(world.get::<Damage>(sword).unwrap().0, 10);
+		assert_eq!(world.get::<Weight>(sword).unwrap().0, 1);
+		assert_eq!(world.get::<PrototypeLayers>(sword).unwrap().len(), 2);
+		assert!(world.get::<Disabled>(sword).is_some());
+
+		let report = world.resource::<UniqueReport>();
+		assert!(report.get_record("Dagger").is_none());
+		assert_eq!(report.overridden().count(), 1);
+
+		// Same key as first merge, non reflected kept
+		let sword_record = report.get_record("Sword").unwrap();
+		let damage = &sword_record[&DebugName::type_name::<Damage>().to_smolstr()];
+		assert_eq!(damage.source, "base");
+		assert!(damage.overridden.is_empty());
+		assert_eq!(
+			sword_record[&DebugName::type_name::<Sharp>().to_smolstr()].source,
+			"base"
+		);
+		assert_eq!(sword_record.len(), 3);
+	}
+}
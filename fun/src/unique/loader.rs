@@ -0,0 +1,319 @@
+use std::{fmt, path::PathBuf};
+
+use bevy_derive::*;
+use bevy_ecs::{entity_disabling::Disabled, prelude::*};
+use bevy_log::prelude::*;
+use bevy_reflect::{PartialReflect, TypeRegistry, serde::TypedReflectDeserializer};
+use serde::de::{DeserializeSeed, Deserializer, Error as _, MapAccess, Visitor};
+use smol_str::{SmolStr, ToSmolStr};
+
+use super::*;
+use crate::serding::ReflectMapSerializer;
+
+/// One prototype from a mod file, every component must be registered with [ReflectComponent].
+#[derive(Debug)]
+pub struct PrototypeDef {
+	pub name: UniqueName,
+	/// File name inside `prototypes`, empty if not from file.
+	pub file: SmolStr,
+	pub components: Vec<Box<dyn PartialReflect>>,
+}
+
+/// Mod waiting in [PendingMods].
+#[derive(Debug)]
+pub struct ModContent {
+	pub manifest: ModManifest,
+	pub prototypes: Vec<PrototypeDef>,
+}
+
+/// Push mod here, loaded all at once in [ModLoadOrder].
+#[derive(Resource, Default, Debug, Deref, DerefMut)]
+pub struct PendingMods(pub Vec<ModContent>);
+
+/// [PendingMods] whose load order failed when loaded by a system, never retried, see [ModsFailed].
+///
+/// Push them back to [PendingMods] once fixed.
+#[derive(Resource, Default, Debug, Deref, DerefMut)]
+pub struct FailedMods(pub Vec<ModContent>);
+
+/// Read `manifest.ron` and every `.ron` inside `prototypes` sorted by file name.
+///
+/// Prototype file is a map of name to map of component type path:
+/// ```ron
+/// {
+///     "Sword": {
+///         "game::Damage": (10),
+///     },
+/// }
+/// ```
+pub fn read_mod_dir(mut path: PathBuf, registry: &TypeRegistry) -> Result<ModContent> {
+	path.push("manifest.ron");
+	let manifest: ModManifest = ron::de::from_bytes(&std::fs::read(&path)?)?;
+	path.pop();
+
+	path.push("prototypes");
+	let mut files = match std::fs::read_dir(&path) {
+		Ok(read_dir) => read_dir
+			.filter_map(|entry| entry.ok().map(|entry| entry.path()))
+			.filter(|file| file.extension().is_some_and(|ext| ext == "ron"))
+			.collect::<Vec<_>>(),
+		Err(err) => {
+			warn!("Mod {} without prototypes: {:?}", manifest.id, err);
+			Vec::new()
+		}
+	};
+	files.sort();
+
+	let mut prototypes = Vec::new();
+	for file in files {
+		let file_name = file.file_name().unwrap_or_default().to_string_lossy().to_smolstr();
+		let mut from_file = read_prototypes(&std::fs::read(file)?, registry)?;
+		from_file
+			.iter_mut()
+			.for_each(|prototype| prototype.file = file_name.clone());
+		prototypes.extend(from_file);
+	}
+
+	Ok(ModContent { manifest, prototypes })
+}
+
+pub fn read_prototypes(data: &[u8], registry: &TypeRegistry) -> Result<Vec<PrototypeDef>> {
+	let mut deserializer = ron::Deserializer::from_bytes(data)?;
+	Ok(PrototypeFileSeed(registry).deserialize(&mut deserializer)?)
+}
+
+struct PrototypeFileSeed<'a>(&'a TypeRegistry);
+
+impl<'a, 'de> DeserializeSeed<'de> for PrototypeFileSeed<'a> {
+	type Value = Vec<PrototypeDef>;
+
+	fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+		deserializer.deserialize_map(self)
+	}
+}
+
+impl<'a, 'de> Visitor<'de> for PrototypeFileSeed<'a> {
+	type Value = Vec<PrototypeDef>;
+
+	fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+		formatter.write_str("map of unique name to components")
+	}
+
+	fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+		let mut prototypes = Vec::new();
+		while let Some(name) = map.next_key::<String>()? {
+			let components = map.next_value_seed(ComponentsSeed(self.0))?;
+			prototypes.push(PrototypeDef {
+				name: UniqueName::new(&name),
+				file: SmolStr::default(),
+				components,
+			});
+		}
+		Ok(prototypes)
+	}
+}
+
+/// Read what [ReflectMapSerializer] write.
+pub(super) struct ComponentsSeed<'a>(pub(super) &'a TypeRegistry);
+
+impl<'a, 'de> DeserializeSeed<'de> for ComponentsSeed<'a> {
+	type Value = Vec<Box<dyn PartialReflect>>;
+
+	fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+		deserializer.deserialize_map(self)
+	}
+}
+
+impl<'a, 'de> Visitor<'de> for ComponentsSeed<'a> {
+	type Value = Vec<Box<dyn PartialReflect>>;
+
+	fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+		formatter.write_str("map of component type path to value")
+	}
+
+	fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+		let mut components = Vec::new();
+		while let Some(type_path) = map.next_key::<String>()? {
+			let registration = self
+				.0
+				.get_with_type_path(&type_path)
+				.ok_or_else(|| A::Error::custom(format!("Unregistered type: {}", type_path)))?;
+			components.push(map.next_value_seed(TypedReflectDeserializer::new(registration, self.0))?);
+		}
+		Ok(components)
+	}
+}
+
+/// Spawn every [PendingMods] prototype in [ModLoadOrder], so the last mod always win the merge.
+///
+/// Unqualified name get the mod namespace, see [UniqueName::qualified].
+///
+/// Called by [FinalizeUnique], [validate_prototypes] run after.
+/// Mod may depend on one already in [ModLoadOrder], [PendingMods] kept if order fail.
+pub(super) fn load_mods(world: &mut World) -> Result<(), LoadOrderError> {
+	let mut mods = std::mem::take(&mut world.resource_mut::<PendingMods>().0);
+	let manifests = mods.iter().map(|content| content.manifest.clone()).collect::<Vec<_>>();
+	let order = match resolve_load_order_after(&world.resource::<ModLoadOrder>().0, &manifests) {
+		Ok(order) => order,
+		Err(err) => {
+			world.resource_mut::<PendingMods>().0 = mods;
+			return Err(err);
+		}
+	};
+
+	mods.sort_by_key(|content| order.iter().position(|id| *id == content.manifest.id));
+
+	let registry = world.resource::<AppTypeRegistry>().clone();
+	let registry = registry.read();
+	for content in mods {
+		for PrototypeDef { name, file, components } in content.prototypes {
+			let source = PrototypeSource {
+				mod_id: content.manifest.id.clone(),
+				file,
+			};
+			let name = name.qualified(&content.manifest.id);
+			spawn_prototype(world, &registry, source, name, &components);
+		}
+	}
+	drop(registry);
+
+	world.resource_mut::<ModLoadOrder>().0.extend(order);
+	Ok(())
+}
+
+/// [UniqueName] inserted last, so [UniqueEntity] merge see every component.
+pub fn spawn_prototype(
+	world: &mut World,
+	registry: &TypeRegistry,
+	source: PrototypeSource,
+	name: UniqueName,
+	components: &[Box<dyn PartialReflect>],
+) -> Entity {
+	let mut ent_mut = world.spawn((Disabled, source));
+	for component in components {
+		match component
+			.get_represented_type_info()
+			.and_then(|type_info| registry.get_type_data::<ReflectComponent>(type_info.type_id()))
+		{
+			Some(reflect_component) => reflect_component.insert(&mut ent_mut, component.as_ref(), registry),
+			None => warn!(
+				"Prototype {} has unregistered component: {}",
+				name.0,
+				component.reflect_type_path()
+			),
+		}
+	}
+	ent_mut.insert(name).id()
+}
+
+pub(super) fn has_pending_mods(pending: Res<PendingMods>) -> bool {
+	!pending.is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use bevy_reflect::Reflect;
+
+	#[derive(Component, Reflect, Default)]
+	#[reflect(Component)]
+	struct Damage(u32);
+
+	#[derive(Component, Reflect, Default)]
+	#[reflect(Component)]
+	struct Weight(u32);
+
+	fn manifest(id: &str, dependencies: &[&str]) -> ModManifest {
+		ModManifest {
+			id: SmolStr::new(id),
+			dependencies: dependencies.iter().map(SmolStr::new).collect(),
+			..Default::default()
+		}
+	}
+
+	#[test]
+	fn test_load_mods() {
+		let mut app = App::new();
+		app.add_plugins(UniquePlugin)
+			.register_type::<Damage>()
+			.register_type::<Weight>()
+			.add_systems(Startup, setup)
+			.add_systems(Last, checkout);
+
+		app.run();
+	}
+
+	fn setup(world: &mut World) {
+		let registry = world.resource::<AppTypeRegistry>().clone();
+		let registry = registry.read();
+
+		let patch = br#"{
+			"base:Sword": {
+				"reflection_fun::unique::loader::tests::Damage": (25),
+			},
+		}"#;
+		let base = br#"{
+			"Sword": {
+				"reflection_fun::unique::loader::tests::Damage": (10),
+				"reflection_fun::unique::loader::tests::Weight": (3),
+			},
+			"Shield": {
+				"reflection_fun::unique::loader::tests::Weight": (8),
+			},
+		}"#;
+
+		// Patch pushed first, but depend on base
+		let mut pending = world.resource_mut::<PendingMods>();
+		pending.push(ModContent {
+			manifest: manifest("patch", &["base"]),
+			prototypes: read_prototypes(patch, &registry).unwrap(),
+		});
+		pending.push(ModContent {
+			manifest: manifest("base", &[]),
+			prototypes: read_prototypes(base, &registry).unwrap(),
+		});
+	}
+
+	fn checkout(world: &mut World) {
+		assert_eq!(world.resource::<ModLoadOrder>().0, ["base", "patch"]);
+
+		let hashed = world.resource::<UniqueHashed>();
+		assert_eq!(hashed.len(), 2);
+		let sword = hashed.get_ent("base:Sword").unwrap();
+		let shield = hashed.get_ent("base:Shield").unwrap();
+
+		assert_eq!(world.get::<Damage>(sword).unwrap().0, 25);
+		assert_eq!(world.get::<Weight>(sword).unwrap().0, 3);
+		assert_eq!(world.get::<Weight>(shield).unwrap().0, 8);
+	}
+
+	#[test]
+	fn test_load_after_loaded() {
+		let mut app = App::new();
+		app.add_plugins(UniquePlugin);
+		let world = app.world_mut();
+		let content = |id: &str, dependencies: &[&str]| ModContent {
+			manifest: manifest(id, dependencies),
+			prototypes: Vec::new(),
+		};
+
+		world.resource_mut::<PendingMods>().push(content("base", &[]));
+		load_mods(world).unwrap();
+		world.resource_mut::<PendingMods>().push(content("patch", &["base"]));
+		load_mods(world).unwrap();
+		assert_eq!(world.resource::<ModLoadOrder>().0, ["base", "patch"]);
+
+		// Kept pending, nothing loaded
+		world
+			.resource_mut::<PendingMods>()
+			.push(content("broken", &["not_installed"]));
+		world.resource_mut::<PendingMods>().push(content("extra", &["base"]));
+		assert!(load_mods(world).is_err());
+		assert_eq!(world.resource::<PendingMods>().len(), 2);
+		assert_eq!(world.resource::<ModLoadOrder>().len(), 2);
+
+		world.resource_mut::<PendingMods>().push(content("base", &[]));
+		assert!(load_mods(world).is_err());
+		assert_eq!(world.resource::<PendingMods>().len(), 3);
+	}
+}
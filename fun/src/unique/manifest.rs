@@ -0,0 +1,195 @@
+use std::{
+	collections::{BTreeMap, BTreeSet},
+	fmt,
+};
+
+use bevy_derive::*;
+use bevy_ecs::prelude::*;
+use bevy_reflect::Reflect;
+use serde::{Deserialize, Serialize};
+use smol_str::SmolStr;
+
+/// Read from `manifest.ron` at the root of mod folder.
+#[derive(Reflect, Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(default)]
+pub struct ModManifest {
+	pub id: SmolStr,
+	pub version: SmolStr,
+	/// Must exist, always loaded before this mod.
+	pub dependencies: Vec<SmolStr>,
+	/// Loaded before this mod only if exist.
+	pub load_after: Vec<SmolStr>,
+	/// Loaded after this mod only if exist.
+	pub load_before: Vec<SmolStr>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LoadOrderError {
+	DuplicateId(SmolStr),
+	MissingDependency {
+		id: SmolStr,
+		dependency: SmolStr,
+	},
+	/// Mods in the cycle, first one is repeated at the end.
+	Cycle(Vec<SmolStr>),
+}
+
+impl fmt::Display for LoadOrderError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			LoadOrderError::DuplicateId(id) => write!(f, "Duplicate mod id: {}", id),
+			LoadOrderError::MissingDependency { id, dependency } => {
+				write!(f, "Mod {} missing dependency: {}", id, dependency)
+			}
+			LoadOrderError::Cycle(ids) => write!(f, "Mod load order cycle: {}", ids.join(" -> ")),
+		}
+	}
+}
+
+impl std::error::Error for LoadOrderError {}
+
+/// Resolved by [resolve_load_order], later mod override earlier one.
+#[derive(Resource, Default, Debug, Clone, Deref)]
+pub struct ModLoadOrder(pub Vec<SmolStr>);
+
+/// Topological sort of mods, tie broken by id so result never depend on file system.
+pub fn resolve_load_order(manifests: &[ModManifest]) -> Result<Vec<SmolStr>, LoadOrderError> {
+	resolve_load_order_after(&[], manifests)
+}
+
+/// [resolve_load_order] of mods added after `loaded`, dependency on loaded mod already satisfied.
+pub fn resolve_load_order_after(loaded: &[SmolStr], manifests: &[ModManifest]) -> Result<Vec<SmolStr>, LoadOrderError> {
+	let mut ids = BTreeSet::new();
+	for manifest in manifests {
+		if loaded.contains(&manifest.id) || !ids.insert(manifest.id.clone()) {
+			return Err(LoadOrderError::DuplicateId(manifest.id.clone()));
+		}
+	}
+
+	// Edge: key must load before every value
+	let mut edges: BTreeMap<SmolStr, BTreeSet<SmolStr>> = ids.iter().map(|id| (id.clone(), BTreeSet::new())).collect();
+	for manifest in manifests {
+		for dependency in manifest
+			.dependencies
+			.iter()
+			.filter(|dependency| !loaded.contains(*dependency))
+		{
+			let Some(outgoing) = edges.get_mut(dependency) else {
+				return Err(LoadOrderError::MissingDependency {
+					id: manifest.id.clone(),
+					dependency: dependency.clone(),
+				});
+			};
+			outgoing.insert(manifest.id.clone());
+		}
+		for after in &manifest.load_after {
+			if let Some(outgoing) = edges.get_mut(after) {
+				outgoing.insert(manifest.id.clone());
+			}
+		}
+		for before in manifest.load_before.iter().filter(|before| ids.contains(*before)) {
+			edges.get_mut(&manifest.id).unwrap().insert(before.clone());
+		}
+	}
+
+	let mut in_degree: BTreeMap<SmolStr, usize> = ids.iter().map(|id| (id.clone(), 0)).collect();
+	for to in edges.values().flatten() {
+		*in_degree.get_mut(to).unwrap() += 1;
+	}
+
+	let mut ready: BTreeSet<SmolStr> = in_degree
+		.iter()
+		.filter(|(_, degree)| **degree == 0)
+		.map(|(id, _)| id.clone())
+		.collect();
+	let mut order = Vec::with_capacity(ids.len());
+	while let Some(id) = ready.pop_first() {
+		for to in &edges[&id] {
+			let degree = in_degree.get_mut(to).unwrap();
+			*degree -= 1;
+			if *degree == 0 {
+				ready.insert(to.clone());
+			}
+		}
+		order.push(id);
+	}
+
+	if order.len() == ids.len() {
+		return Ok(order);
+	}
+
+	// Every leftover has a leftover before it, walk backward until repeated
+	let leftover: BTreeSet<&SmolStr> = in_degree
+		.iter()
+		.filter(|(_, degree)| **degree > 0)
+		.map(|(id, _)| id)
+		.collect();
+	let mut walked: Vec<&SmolStr> = vec![leftover.first().unwrap()];
+	loop {
+		let current = *walked.last().unwrap();
+		let previous = edges
+			.iter()
+			.find(|(from, to)| leftover.contains(from) && to.contains(current))
+			.map(|(from, _)| from)
+			.unwrap();
+		if let Some(start) = walked.iter().position(|id| *id == previous) {
+			let mut cycle: Vec<SmolStr> = walked[start..].iter().rev().map(|id| (*id).clone()).collect();
+			cycle.push(cycle[0].clone());
+			return Err(LoadOrderError::Cycle(cycle));
+		}
+		walked.push(previous);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn manifest(id: &str, dependencies: &[&str], load_after: &[&str], load_before: &[&str]) -> ModManifest {
+		let to_vec = |ids: &[&str]| ids.iter().map(SmolStr::new).collect();
+		ModManifest {
+			id: SmolStr::new(id),
+			version: SmolStr::new("0.1.0"),
+			dependencies: to_vec(dependencies),
+			load_after: to_vec(load_after),
+			load_before: to_vec(load_before),
+		}
+	}
+
+	#[test]
+	fn test_load_order() {
+		let manifests = [
+			manifest("zeta", &["core"], &[], &[]),
+			manifest("alpha", &["core"], &["zeta"], &["not_installed"]),
+			manifest("core", &[], &[], &[]),
+			manifest("beta", &[], &[], &["core"]),
+		];
+		let order = resolve_load_order(&manifests).unwrap();
+		assert_eq!(order, ["beta", "core", "zeta", "alpha"]);
+
+		let missing = [manifest("alpha", &["core"], &[], &[])];
+		assert_eq!(
+			resolve_load_order(&missing),
+			Err(LoadOrderError::MissingDependency {
+				id: SmolStr::new("alpha"),
+				dependency: SmolStr::new("core"),
+			})
+		);
+
+		let cycled = [
+			manifest("core", &[], &[], &[]),
+			manifest("alpha", &["beta"], &[], &[]),
+			manifest("beta", &["core"], &["gamma"], &[]),
+			manifest("gamma", &[], &["alpha"], &[]),
+		];
+		assert_eq!(
+			resolve_load_order(&cycled),
+			Err(LoadOrderError::Cycle(vec![
+				SmolStr::new("gamma"),
+				SmolStr::new("beta"),
+				SmolStr::new("alpha"),
+				SmolStr::new("gamma"),
+			]))
+		);
+	}
+}
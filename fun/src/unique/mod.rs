@@ -0,0 +1,306 @@
+#![allow(unused)]
+use std::any::TypeId;
+
+use bevy_app::prelude::*;
+use bevy_derive::*;
+use bevy_ecs::{
+	entity_disabling::Disabled, lifecycle::HookContext, prelude::*, world::DeferredWorld,
+};
+use bevy_log::prelude::*;
+use bevy_platform::{collections::HashMap, prelude::*};
+use smol_str::SmolStr;
+
+mod event;
+mod export;
+mod fingerprint;
+mod id;
+mod instance;
+mod layer;
+mod loader;
+mod manifest;
+mod namespace;
+mod param;
+mod phase;
+mod report;
+mod save;
+mod table;
+mod tag;
+mod validate;
+
+pub use event::*;
+pub use export::*;
+pub use fingerprint::*;
+pub use id::*;
+pub use instance::*;
+pub use layer::*;
+pub use loader::*;
+pub use manifest::*;
+pub use namespace::*;
+pub use param::*;
+pub use phase::*;
+pub use report::*;
+pub use save::*;
+pub use table::*;
+pub use tag::*;
+pub use validate::*;
+
+#[derive(Default)]
+pub struct UniquePlugin;
+impl Plugin for UniquePlugin {
+	fn build(&self, app: &mut App) {
+		app.init_resource::<UniqueHashed>()
+			.init_resource::<PendingMods>()
+			.init_resource::<FailedMods>()
+			.init_resource::<ModLoadOrder>()
+			.init_resource::<UniqueReport>()
+			.init_resource::<UniqueIndex>()
+			.init_resource::<ValidationRules>()
+			.init_resource::<ValidationReport>()
+			.init_resource::<UniquePhase>()
+			.init_resource::<UniqueSettings>()
+			.register_type::<UniqueReport>()
+			.register_type::<PrototypeSource>()
+			.register_type::<UniqueTags>()
+			.register_type::<UniqueRef>()
+			.register_type::<PrototypeParams>()
+			.register_type::<PrototypeArgs>()
+			.register_type::<UniqueFingerprint>()
+			.add_systems(
+				PreUpdate,
+				(
+					auto_finalize.run_if(can_auto_finalize),
+					load_late_mods.run_if(unique_frozen.and(has_pending_mods)),
+				)
+					.chain(),
+			);
+	}
+}
+
+/// Search by name or [UniqueId], Entity are [UniqueEntity] and [UniqueName] with [Disabled]
+#[derive(Resource, Default, Debug, Deref)]
+pub struct UniqueHashed {
+	#[deref]
+	entities: HashMap<UniqueName, Entity>,
+	ids: HashMap<UniqueName, UniqueId>,
+	names: HashMap<UniqueId, UniqueName>,
+}
+
+impl UniqueHashed {
+	pub fn get_ent(&self, name: &str) -> Option<Entity> {
+		self.get(&UniqueName::new(name)).cloned()
+	}
+}
+
+#[derive(Component, Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+#[require(UniqueEntity)]
+#[component(immutable)]
+pub struct UniqueName(pub SmolStr);
+
+impl UniqueName {
+	pub fn new(token: &str) -> Self {
+		Self(SmolStr::new(token))
+	}
+}
+
+/// Component used to keep track of unique, never merged or layered.
+fn is_unique_internal(type_id: TypeId) -> bool {
+	[
+		TypeId::of::<UniqueName>(),
+		TypeId::of::<UniqueEntity>(),
+		TypeId::of::<Disabled>(),
+		TypeId::of::<PrototypeSource>(),
+		TypeId::of::<PrototypeLayers>(),
+		TypeId::of::<PrototypeInstances>(),
+		TypeId::of::<UniqueFingerprint>(),
+	]
+	.contains(&type_id)
+}
+
+/// Must insert with [Name], technique used to make modding easy, maybe.
+///
+/// Contain any component used to clone.
+#[derive(Component, Default)]
+#[require(Disabled)]
+#[component(on_add = UniqueEntity::on_add, on_remove = UniqueEntity::on_remove)]
+pub struct UniqueEntity;
+
+impl UniqueEntity {
+	fn on_add(mut world: DeferredWorld, HookContext { entity, .. }: HookContext) {
+		let Some(name) = world.entity(entity).get::<UniqueName>().cloned() else {
+			warn!("Adding Unique Component without Name: {}", entity);
+			world.commands().entity(entity).despawn();
+			return;
+		};
+		if !accept_addition(*world.resource::<UniquePhase>(), world.resource::<UniqueSettings>(), &name) {
+			world.commands().entity(entity).despawn();
+			return;
+		}
+		let mut hashed = world.resource_mut::<UniqueHashed>();
+		match hashed.get(&name).cloned() {
+			Some(ent_hashed) => {
+				world.commands().queue(move |inner_world: &mut World| {
+					// Reflected component applied by layer, only move the rest
+					push_layer(inner_world, ent_hashed, entity);
+
+					let registry = inner_world.resource::<AppTypeRegistry>().clone();
+					let registry = registry.read();
+					let ent_ref = inner_world.entity(entity);
+					let components = ent_ref
+						.archetype()
+						.iter_components()
+						.filter(|component_id| {
+							let type_id = inner_world
+								.components()
+								.get_info(*component_id)
+								.and_then(|info| info.type_id());
+							type_id.is_none_or(|type_id| registry.get_type_data::<ReflectComponent>(type_id).is_none())
+						})
+						.collect::<Vec<_>>();
+
+					// Removed from the contributor, so not dropped again on despawn
+					inner_world
+						.entity_mut(entity)
+						.clone_with_opt_in(ent_hashed, move |builder| {
+							builder
+								.without_required_components(|builder| {
+									builder.allow_by_ids(components);
+								})
+								.move_components(true);
+						});
+
+					propagate_to_instances(inner_world, ent_hashed);
+					inner_world.trigger(UniqueOverridden {
+						name,
+						entity: ent_hashed,
+						contributor: entity,
+					});
+
+					inner_world.commands().entity(entity).despawn();
+				});
+			}
+			None => {
+				hashed.register(name.clone(), entity);
+				let tags = world.entity(entity).get::<UniqueTags>().cloned();
+				let mut index = world.resource_mut::<UniqueIndex>();
+				index.insert_name(&name);
+				if let Some(tags) = tags {
+					index.insert_tags(&name, &tags);
+				}
+				world.trigger(UniqueRegistered {
+					name: name.clone(),
+					entity,
+				});
+				world
+					.commands()
+					.queue(move |inner_world: &mut World| push_layer(inner_world, entity, entity));
+			}
+		};
+	}
+
+	fn on_remove(mut world: DeferredWorld, HookContext { entity, .. }: HookContext) {
+		let Some(name) = world.entity(entity).get::<UniqueName>().cloned() else {
+			warn!("Removing Unique Component without Name: {}", entity);
+			return;
+		};
+		let mut hashed = world.resource_mut::<UniqueHashed>();
+		if let Some(ent_hashed) = hashed.get(&name) {
+			if &entity == ent_hashed {
+				hashed.unregister(&name);
+				world.resource_mut::<UniqueReport>().remove(&name);
+				world.resource_mut::<UniqueIndex>().remove_name(&name);
+				world.trigger(UniqueRemoved { name, entity });
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::sync::atomic::{AtomicUsize, Ordering};
+
+	#[test]
+	fn test_unique() {
+		let mut app = App::new();
+		app.add_plugins(UniquePlugin)
+			.add_systems(Startup, setup)
+			.add_systems(Last, checkout);
+
+		app.run();
+	}
+
+	#[derive(Component)]
+	struct A(u8);
+	#[derive(Component)]
+	struct B(u8);
+	#[derive(Component)]
+	struct C(f32);
+
+	fn setup(world: &mut World) {
+		world.spawn((UniqueName::new("Test_1"), A(10)));
+		world.spawn((UniqueName::new("Test_1"), B(5)));
+		world.spawn((UniqueName::new("Test_1"), C(5.05)));
+
+		world.spawn((UniqueName::new("Test_2"), A(100)));
+		world.spawn((UniqueName::new("Test_2"), B(55)));
+		world.spawn((UniqueName::new("Test_2"), C(56.25)));
+
+		world.spawn((UniqueName::new("Test_3"), B(60)));
+
+		let ent_1 = world.spawn((UniqueName::new("Test_4"), A(100))).id();
+		let ent_2 = world.spawn((UniqueName::new("Test_5"), B(58))).id();
+
+		world.commands().entity(ent_1).despawn();
+		world.commands().entity(ent_2).despawn();
+
+		let hashed = world.resource::<UniqueHashed>();
+		assert_eq!(hashed.len(), 5);
+	}
+
+	fn checkout(world: &mut World) {
+		let mut q_ent = world.query_filtered::<Entity, (With<UniqueEntity>, With<Disabled>)>();
+		let out_ent = q_ent.iter(world);
+		assert_eq!(out_ent.count(), 3);
+
+		let mut q_b = world
+			.query_filtered::<&B, (With<UniqueEntity>, With<Disabled>, Without<A>, Without<C>)>();
+		let out_b = q_b.single(world).unwrap();
+		assert_eq!(out_b.0, 60);
+
+		let mut q_out = world.query_filtered::<(Entity, &A, &B, &C), With<Disabled>>();
+		let out = q_out.iter(world);
+		assert_eq!(out.count(), 2);
+
+		let hashed = world.resource::<UniqueHashed>();
+		assert_eq!(hashed.len(), 3);
+	}
+
+	static DROPPED: AtomicUsize = AtomicUsize::new(0);
+
+	#[derive(Component)]
+	struct Dropping(u8);
+
+	impl Drop for Dropping {
+		fn drop(&mut self) {
+			DROPPED.fetch_add(1, Ordering::SeqCst);
+		}
+	}
+
+	/// Moved out of the contributor, dropped once with the prototype
+	#[test]
+	fn test_merge_drop_once() {
+		let mut app = App::new();
+		app.add_plugins(UniquePlugin);
+		let world = app.world_mut();
+		let base = world.spawn((UniqueName::new("Test_1"), A(10))).id();
+		let contributor = world.spawn((UniqueName::new("Test_1"), Dropping(5))).id();
+		world.flush();
+		world.flush();
+
+		assert!(world.get_entity(contributor).is_err());
+		assert_eq!(world.get::<Dropping>(base).unwrap().0, 5);
+		assert_eq!(DROPPED.load(Ordering::SeqCst), 0);
+		world.despawn(base);
+		assert_eq!(DROPPED.load(Ordering::SeqCst), 1);
+	}
+}
@@ -0,0 +1,117 @@
+use bevy_ecs::prelude::*;
+use smol_str::{SmolStr, format_smolstr};
+
+use super::*;
+
+/// Fallback namespace of unqualified name, base game content.
+pub const CORE_NAMESPACE: &str = "core";
+
+/// Separator of `mod_id:name`.
+pub const NAMESPACE_SEPARATOR: char = ':';
+
+/// Namespaced name `mod_id:name`.
+///
+/// Prototype loaded from a mod get qualified with its [ModManifest::id], unless already qualified.
+/// Writing another mod namespace is the explicit override, `"core:sword"` inside `mymod` merge into core sword
+/// while `"sword"` define `mymod:sword`.
+impl UniqueName {
+	pub fn namespaced(namespace: &str, name: &str) -> Self {
+		Self(format_smolstr!("{}{}{}", namespace, NAMESPACE_SEPARATOR, name))
+	}
+
+	/// [None] if unqualified.
+	pub fn namespace(&self) -> Option<&str> {
+		self.0.split_once(NAMESPACE_SEPARATOR).map(|(namespace, _)| namespace)
+	}
+
+	/// Name without namespace.
+	pub fn local(&self) -> &str {
+		self.0
+			.split_once(NAMESPACE_SEPARATOR)
+			.map_or(self.0.as_str(), |(_, local)| local)
+	}
+
+	pub fn is_qualified(&self) -> bool {
+		self.namespace().is_some()
+	}
+
+	/// Qualify with the namespace if unqualified.
+	pub fn qualified(self, namespace: &str) -> Self {
+		match self.is_qualified() {
+			true => self,
+			false => Self::namespaced(namespace, &self.0),
+		}
+	}
+}
+
+impl UniqueHashed {
+	/// Resolve name referenced from `from_mod`.
+	///
+	/// Qualified name is exact, unqualified try `from_mod:name`, then `core:name`, then `name` as is.
+	pub fn resolve(&self, name: &str, from_mod: &str) -> Option<(&UniqueName, Entity)> {
+		let name = UniqueName::new(name);
+		let candidates = match name.is_qualified() {
+			true => vec![name],
+			false => vec![
+				UniqueName::namespaced(from_mod, &name.0),
+				UniqueName::namespaced(CORE_NAMESPACE, &name.0),
+				name,
+			],
+		};
+		candidates
+			.iter()
+			.find_map(|candidate| self.get_key_value(candidate))
+			.map(|(name, entity)| (name, *entity))
+	}
+
+	pub fn resolve_ent(&self, name: &str, from_mod: &str) -> Option<Entity> {
+		self.resolve(name, from_mod).map(|(_, entity)| entity)
+	}
+
+	/// Every name in the namespace, unordered.
+	pub fn in_namespace<'a>(&'a self, namespace: &'a str) -> impl Iterator<Item = (&'a UniqueName, Entity)> {
+		self.iter()
+			.filter(move |(name, _)| name.namespace() == Some(namespace))
+			.map(|(name, entity)| (name, *entity))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_namespace() {
+		let mut app = App::new();
+		app.add_plugins(UniquePlugin)
+			.add_systems(Startup, setup)
+			.add_systems(Last, checkout);
+
+		app.run();
+	}
+
+	fn setup(world: &mut World) {
+		world.spawn(UniqueName::new("core:sword"));
+		world.spawn(UniqueName::new("core:shield"));
+		world.spawn(UniqueName::new("mymod:sword"));
+		world.spawn(UniqueName::new("legacy"));
+	}
+
+	fn checkout(hashed: Res<UniqueHashed>) {
+		let name = UniqueName::new("sword").qualified("mymod");
+		assert_eq!(name.namespace(), Some("mymod"));
+		assert_eq!(name.local(), "sword");
+		assert_eq!(UniqueName::new("legacy").local(), "legacy");
+
+		let resolved = |name: &str, from_mod: &str| hashed.resolve(name, from_mod).map(|(name, _)| name.0.as_str());
+		assert_eq!(resolved("sword", "mymod"), Some("mymod:sword"));
+		assert_eq!(resolved("sword", "other"), Some("core:sword"));
+		assert_eq!(resolved("shield", "mymod"), Some("core:shield"));
+		assert_eq!(resolved("core:sword", "mymod"), Some("core:sword"));
+		assert_eq!(resolved("legacy", "mymod"), Some("legacy"));
+		assert_eq!(resolved("other:sword", "mymod"), None);
+
+		assert_eq!(hashed.in_namespace(CORE_NAMESPACE).count(), 2);
+		assert_eq!(hashed.resolve_ent("sword", "mymod"), hashed.get_ent("mymod:sword"));
+	}
+}
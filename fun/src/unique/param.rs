@@ -0,0 +1,322 @@
+use std::{any::TypeId, collections::BTreeMap, fmt};
+
+use bevy_derive::*;
+use bevy_ecs::prelude::*;
+use bevy_log::prelude::*;
+use bevy_reflect::{PartialReflect, Reflect, ReflectPath, TypeRegistry};
+use serde::{Deserialize, Serialize};
+use smol_str::{SmolStr, ToSmolStr};
+
+use super::*;
+
+/// Named parameter of the prototype, substituted into instance by [InstanceFrom].
+///
+/// ```ron
+/// "reflection_fun::unique::param::PrototypeParams": ([
+///     (name: "level", component: "game::Level", path: ".0", default: Int(1)),
+/// ]),
+/// ```
+#[derive(Component, Reflect, Serialize, Deserialize, Clone, Debug, Default, PartialEq, Deref)]
+#[reflect(Component)]
+pub struct PrototypeParams(pub Vec<PrototypeParam>);
+
+#[derive(Reflect, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct PrototypeParam {
+	pub name: SmolStr,
+	/// Type path of the component.
+	pub component: SmolStr,
+	/// Reflect path of the field, such as `.0` or `stats.level`.
+	pub path: SmolStr,
+	pub default: ParamValue,
+}
+
+#[derive(Reflect, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum ParamValue {
+	/// Any integer field in range, or float field.
+	Int(i64),
+	/// `f32` or `f64` field.
+	Float(f64),
+	Bool(bool),
+	/// `String` or [SmolStr] field.
+	Str(SmolStr),
+}
+
+/// Argument the instance was spawned with, kept so prototype change are substituted the same.
+#[derive(Component, Reflect, Serialize, Deserialize, Clone, Debug, Default, PartialEq, Deref, DerefMut)]
+#[reflect(Component)]
+pub struct PrototypeArgs(pub BTreeMap<SmolStr, ParamValue>);
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParamError {
+	UnknownParam(SmolStr),
+	/// Parameter component not on the prototype.
+	MissingComponent {
+		param: SmolStr,
+		component: SmolStr,
+	},
+	InvalidPath {
+		param: SmolStr,
+		path: SmolStr,
+	},
+	Mismatch {
+		param: SmolStr,
+		field: SmolStr,
+	},
+}
+
+impl fmt::Display for ParamError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			ParamError::UnknownParam(param) => write!(f, "Unknown parameter {}", param),
+			ParamError::MissingComponent { param, component } => {
+				write!(f, "Parameter {} component {} missing", param, component)
+			}
+			ParamError::InvalidPath { param, path } => write!(f, "Parameter {} no field {}", param, path),
+			ParamError::Mismatch { param, field } => write!(f, "Parameter {} not fit into {}", param, field),
+		}
+	}
+}
+
+macro_rules! set_int {
+	($field:ident, $value:expr, $($ty:ty),*) => {
+		$(if let Some(field) = $field.try_downcast_mut::<$ty>() {
+			return <$ty>::try_from($value).map(|value| *field = value).is_ok();
+		})*
+	};
+}
+
+impl ParamValue {
+	/// `false` if the field type not fit.
+	pub fn set(&self, field: &mut dyn PartialReflect) -> bool {
+		match self {
+			ParamValue::Int(value) => {
+				set_int!(
+					field, *value, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize
+				);
+				ParamValue::Float(*value as f64).set(field)
+			}
+			ParamValue::Float(value) => {
+				if let Some(field) = field.try_downcast_mut::<f32>() {
+					*field = *value as f32;
+				} else if let Some(field) = field.try_downcast_mut::<f64>() {
+					*field = *value;
+				} else {
+					return false;
+				}
+				true
+			}
+			ParamValue::Bool(value) => field.try_downcast_mut::<bool>().map(|field| *field = *value).is_some(),
+			ParamValue::Str(value) => {
+				if let Some(field) = field.try_downcast_mut::<String>() {
+					*field = value.to_string();
+				} else if let Some(field) = field.try_downcast_mut::<SmolStr>() {
+					*field = value.clone();
+				} else {
+					return false;
+				}
+				true
+			}
+		}
+	}
+}
+
+impl PrototypeParams {
+	/// Substitute every parameter into the components, missing or mismatched argument use the default.
+	pub fn substitute(&self, args: &PrototypeArgs, components: &mut [Box<dyn PartialReflect>]) -> Vec<ParamError> {
+		let mut errors = args
+			.keys()
+			.filter(|arg| !self.iter().any(|param| param.name == **arg))
+			.map(|arg| ParamError::UnknownParam(arg.clone()))
+			.collect::<Vec<_>>();
+
+		for param in self.iter() {
+			let value = args.get(&param.name).unwrap_or(&param.default);
+			let Some(component) = components.iter_mut().find(|component| {
+				component
+					.get_represented_type_info()
+					.is_some_and(|type_info| type_info.type_path() == param.component)
+			}) else {
+				errors.push(ParamError::MissingComponent {
+					param: param.name.clone(),
+					component: param.component.clone(),
+				});
+				continue;
+			};
+			let Ok(field) = param.path.as_str().reflect_element_mut(component.as_mut()) else {
+				errors.push(ParamError::InvalidPath {
+					param: param.name.clone(),
+					path: param.path.clone(),
+				});
+				continue;
+			};
+			if !value.set(field) {
+				errors.push(ParamError::Mismatch {
+					param: param.name.clone(),
+					field: field.reflect_type_path().to_smolstr(),
+				});
+				// Fall back to the default, prototype value kept if it does not fit either
+				param.default.set(field);
+			}
+		}
+		errors
+	}
+}
+
+/// Prototype components as an instance get them, [PrototypeParams] substituted and left out.
+pub(super) fn instance_components(
+	world: &World,
+	registry: &TypeRegistry,
+	prototype: Entity,
+	args: &PrototypeArgs,
+) -> Vec<Box<dyn PartialReflect>> {
+	let mut components = snapshot_components(world, registry, prototype);
+	components.retain(|component| {
+		component
+			.get_represented_type_info()
+			.is_none_or(|type_info| type_info.type_id() != TypeId::of::<PrototypeParams>())
+	});
+
+	if let Some(params) = world.get::<PrototypeParams>(prototype) {
+		for error in params.substitute(args, &mut components) {
+			warn!("Prototype {} parameter: {}", prototype, error);
+		}
+	}
+	components
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[derive(Component, Reflect, Default)]
+	#[reflect(Component)]
+	struct Level(u8);
+
+	#[derive(Component, Reflect, Default)]
+	#[reflect(Component)]
+	struct Owner {
+		name: String,
+		hostile: bool,
+	}
+
+	#[derive(Resource)]
+	struct Spawned {
+		default: Entity,
+		custom: Entity,
+		invalid: Entity,
+	}
+
+	#[test]
+	fn test_params() {
+		let mut app = App::new();
+		app.add_plugins(UniquePlugin)
+			.register_type::<Level>()
+			.register_type::<Owner>()
+			.add_systems(PreStartup, setup)
+			.add_systems(Startup, spawn_instances)
+			.add_systems(Last, checkout);
+
+		app.run();
+	}
+
+	fn param(name: &str, component: &str, path: &str, default: ParamValue) -> PrototypeParam {
+		PrototypeParam {
+			name: SmolStr::new(name),
+			component: SmolStr::new(component),
+			path: SmolStr::new(path),
+			default,
+		}
+	}
+
+	fn setup(world: &mut World) {
+		let params = PrototypeParams(vec![
+			param(
+				"level",
+				"reflection_fun::unique::param::tests::Level",
+				".0",
+				ParamValue::Int(1),
+			),
+			param(
+				"owner",
+				"reflection_fun::unique::param::tests::Owner",
+				"name",
+				ParamValue::Str(SmolStr::new("Nobody")),
+			),
+			param(
+				"hostile",
+				"reflection_fun::unique::param::tests::Owner",
+				"hostile",
+				ParamValue::Bool(false),
+			),
+		]);
+		world.spawn((
+			params,
+			Level(0),
+			Owner {
+				name: String::new(),
+				hostile: true,
+			},
+			UniqueName::new("Goblin"),
+		));
+	}
+
+	fn spawn_instances(mut cmd: Commands) {
+		let default = cmd.spawn_empty().queue(InstanceFrom::new("Goblin", true)).id();
+		let custom = cmd
+			.spawn_empty()
+			.queue(
+				InstanceFrom::new("Goblin", true)
+					.with_arg("level", ParamValue::Int(12))
+					.with_arg("owner", ParamValue::Str(SmolStr::new("Chssam"))),
+			)
+			.id();
+		// Out of u8 range and wrong type, fall back to the param default
+		let invalid = cmd
+			.spawn_empty()
+			.queue(
+				InstanceFrom::new("Goblin", false)
+					.with_arg("level", ParamValue::Int(300))
+					.with_arg("hostile", ParamValue::Float(1.0)),
+			)
+			.id();
+		cmd.insert_resource(Spawned {
+			default,
+			custom,
+			invalid,
+		});
+	}
+
+	fn checkout(world: &mut World) {
+		let spawned = world.resource::<Spawned>();
+		let (default, custom, invalid) = (spawned.default, spawned.custom, spawned.invalid);
+
+		assert_eq!(world.get::<Level>(default).unwrap().0, 1);
+		assert_eq!(world.get::<Owner>(default).unwrap().name, "Nobody");
+		assert!(!world.get::<Owner>(default).unwrap().hostile);
+		assert!(world.get::<PrototypeParams>(default).is_none());
+
+		assert_eq!(world.get::<Level>(custom).unwrap().0, 12);
+		assert_eq!(world.get::<Owner>(custom).unwrap().name, "Chssam");
+
+		assert_eq!(world.get::<Level>(invalid).unwrap().0, 1);
+		assert!(!world.get::<Owner>(invalid).unwrap().hostile);
+
+		let registry = world.resource::<AppTypeRegistry>().read();
+		let goblin = world.resource::<UniqueHashed>().get_ent("Goblin").unwrap();
+		let params = world.get::<PrototypeParams>(goblin).unwrap();
+		let mut components = snapshot_components(world, &registry, goblin);
+		let mut args = PrototypeArgs::default();
+		args.insert(SmolStr::new("level"), ParamValue::Str(SmolStr::new("high")));
+		args.insert(SmolStr::new("speed"), ParamValue::Int(2));
+		assert_eq!(
+			params.substitute(&args, &mut components),
+			[
+				ParamError::UnknownParam(SmolStr::new("speed")),
+				ParamError::Mismatch {
+					param: SmolStr::new("level"),
+					field: SmolStr::new("u8"),
+				},
+			]
+		);
+	}
+}
@@ -0,0 +1,289 @@
+use bevy_ecs::prelude::*;
+use bevy_log::prelude::*;
+
+use super::*;
+
+/// Where the unique registry is, advanced by [FinalizeUnique] or [UniqueSettings::auto_finalize].
+#[derive(Resource, Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UniquePhase {
+	/// Prototype spawned and [PendingMods] pushed are accepted.
+	#[default]
+	Collecting,
+	/// [PendingMods] loaded and every merge applied.
+	Merging,
+	/// [validate_prototypes] running.
+	Validating,
+	/// [UniqueHashed] complete, late addition handled by [LateAddition].
+	Frozen,
+}
+
+#[derive(Resource, Debug, Clone)]
+pub struct UniqueSettings {
+	/// Finalize on first [PreUpdate], everything from startup is collected.
+	///
+	/// Mods failing the load order moved to [FailedMods], the rest still finalized.
+	pub auto_finalize: bool,
+	pub late_addition: LateAddition,
+}
+
+impl Default for UniqueSettings {
+	fn default() -> Self {
+		Self {
+			auto_finalize: true,
+			late_addition: LateAddition::Warn,
+		}
+	}
+}
+
+/// What to do with prototype added while [UniquePhase::Frozen].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LateAddition {
+	/// Merged and validated again.
+	Warn,
+	/// Despawned, pending mods dropped.
+	Reject,
+}
+
+/// Load, merge, validate and fingerprint everything collected, then freeze.
+///
+/// Back to [UniquePhase::Collecting] with [PendingMods] kept if the load order fail.
+pub struct FinalizeUnique;
+
+impl Command<Result> for FinalizeUnique {
+	fn apply(self, world: &mut World) -> Result {
+		Ok(finalize(world)?)
+	}
+}
+
+fn finalize(world: &mut World) -> Result<(), LoadOrderError> {
+	if *world.resource::<UniquePhase>() != UniquePhase::Collecting {
+		warn!("Unique already finalized");
+		return Ok(());
+	}
+
+	*world.resource_mut::<UniquePhase>() = UniquePhase::Merging;
+	if let Err(err) = load_mods(world) {
+		*world.resource_mut::<UniquePhase>() = UniquePhase::Collecting;
+		return Err(err);
+	}
+	world.flush();
+
+	*world.resource_mut::<UniquePhase>() = UniquePhase::Validating;
+	validate_prototypes(world);
+	fingerprint_prototypes(world);
+
+	*world.resource_mut::<UniquePhase>() = UniquePhase::Frozen;
+	world.trigger(UniqueFinalized);
+	Ok(())
+}
+
+/// Move every [PendingMods] to [FailedMods], so a system does not fail again every frame.
+fn fail_pending_mods(world: &mut World, error: LoadOrderError) {
+	let failed = std::mem::take(&mut world.resource_mut::<PendingMods>().0);
+	let ids = failed
+		.iter()
+		.map(|content| content.manifest.id.clone())
+		.collect::<Vec<_>>();
+	error!("Mods not loaded {:?}: {}", ids, error);
+	world.resource_mut::<FailedMods>().extend(failed);
+	world.trigger(ModsFailed { ids, error });
+}
+
+/// Run condition for system spawning content from prototypes.
+pub fn unique_frozen(phase: Res<UniquePhase>) -> bool {
+	*phase == UniquePhase::Frozen
+}
+
+pub(super) fn auto_finalize(world: &mut World) {
+	if let Err(err) = finalize(world) {
+		fail_pending_mods(world, err);
+		// No pending mod left, the load order can not fail
+		let _ = finalize(world);
+	}
+}
+
+pub(super) fn can_auto_finalize(phase: Res<UniquePhase>, settings: Res<UniqueSettings>) -> bool {
+	settings.auto_finalize && *phase == UniquePhase::Collecting
+}
+
+/// Mods pushed after [UniquePhase::Frozen].
+pub(super) fn load_late_mods(world: &mut World) {
+	if world.resource::<UniqueSettings>().late_addition == LateAddition::Reject {
+		let rejected = std::mem::take(&mut world.resource_mut::<PendingMods>().0);
+		for content in rejected {
+			warn!("Reject mod {}, unique already frozen", content.manifest.id);
+		}
+		return;
+	}
+
+	warn!("Loading mods after unique frozen");
+	if let Err(err) = load_mods(world) {
+		fail_pending_mods(world, err);
+		return;
+	}
+	world.flush();
+	validate_prototypes(world);
+	fingerprint_prototypes(world);
+}
+
+/// Whether the unique may be added, checked in [UniqueEntity] on add.
+pub(super) fn accept_addition(phase: UniquePhase, settings: &UniqueSettings, name: &UniqueName) -> bool {
+	if phase != UniquePhase::Frozen {
+		return true;
+	}
+	match settings.late_addition {
+		LateAddition::Warn => {
+			warn!("Unique {} added after frozen", name.0);
+			true
+		}
+		LateAddition::Reject => {
+			warn!("Unique {} rejected, already frozen", name.0);
+			false
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[derive(Resource, Default)]
+	struct Finalized(u8);
+
+	#[test]
+	fn test_finalize() {
+		let mut app = App::new();
+		app.add_plugins(UniquePlugin)
+			.insert_resource(UniqueSettings {
+				auto_finalize: false,
+				late_addition: LateAddition::Reject,
+			})
+			.init_resource::<Finalized>()
+			.add_observer(|_: On<UniqueFinalized>, mut finalized: ResMut<Finalized>| {
+				finalized.0 += 1;
+			})
+			.add_systems(Startup, setup)
+			.add_systems(PostStartup, finalize)
+			.add_systems(Update, late.run_if(unique_frozen))
+			.add_systems(Last, checkout);
+
+		app.run();
+	}
+
+	fn setup(world: &mut World) {
+		world.spawn(UniqueName::new("Test_1"));
+		world.spawn(UniqueName::new("Test_1"));
+		world.spawn(UniqueName::new("Test_2"));
+	}
+
+	fn finalize(world: &mut World) {
+		assert_eq!(*world.resource::<UniquePhase>(), UniquePhase::Collecting);
+		world.commands().queue(FinalizeUnique);
+		world.flush();
+		assert_eq!(*world.resource::<UniquePhase>(), UniquePhase::Frozen);
+
+		let mut q_ent = world.query_filtered::<Entity, (With<UniqueEntity>, With<Disabled>)>();
+		assert_eq!(q_ent.iter(world).count(), 2);
+	}
+
+	fn late(world: &mut World) {
+		world.spawn(UniqueName::new("Test_3"));
+	}
+
+	fn checkout(world: &mut World) {
+		assert_eq!(world.resource::<Finalized>().0, 1);
+		assert_eq!(world.resource::<UniqueHashed>().len(), 2);
+
+		let mut q_ent = world.query_filtered::<Entity, (With<UniqueEntity>, With<Disabled>)>();
+		assert_eq!(q_ent.iter(world).count(), 2);
+	}
+
+	#[test]
+	fn test_finalize_bad_manifest() {
+		let mut app = App::new();
+		app.add_plugins(UniquePlugin)
+			.insert_resource(UniqueSettings {
+				auto_finalize: false,
+				late_addition: LateAddition::Reject,
+			})
+			.init_resource::<Finalized>()
+			.add_observer(|_: On<UniqueFinalized>, mut finalized: ResMut<Finalized>| {
+				finalized.0 += 1;
+			});
+		let world = app.world_mut();
+		let manifest = |id: &str, dependencies: &[&str]| ModManifest {
+			id: SmolStr::new(id),
+			dependencies: dependencies.iter().map(SmolStr::new).collect(),
+			..Default::default()
+		};
+		world.resource_mut::<PendingMods>().push(ModContent {
+			manifest: manifest("patch", &["base"]),
+			prototypes: Vec::new(),
+		});
+
+		assert!(FinalizeUnique.apply(world).is_err());
+		assert_eq!(*world.resource::<UniquePhase>(), UniquePhase::Collecting);
+		assert_eq!(world.resource::<PendingMods>().len(), 1);
+		assert_eq!(world.resource::<Finalized>().0, 0);
+
+		// Retry once the dependency is pushed
+		world.resource_mut::<PendingMods>().push(ModContent {
+			manifest: manifest("base", &[]),
+			prototypes: Vec::new(),
+		});
+		FinalizeUnique.apply(world).unwrap();
+		assert_eq!(*world.resource::<UniquePhase>(), UniquePhase::Frozen);
+		assert_eq!(world.resource::<ModLoadOrder>().0, ["base", "patch"]);
+		assert_eq!(world.resource::<Finalized>().0, 1);
+	}
+
+	#[derive(Resource, Default)]
+	struct Failed(Vec<Vec<SmolStr>>);
+
+	/// Reported once and finalized without it, late mod too
+	#[test]
+	fn test_auto_finalize_bad_manifest() {
+		let mut app = App::new();
+		app.add_plugins(UniquePlugin)
+			.init_resource::<Finalized>()
+			.init_resource::<Failed>()
+			.add_observer(|_: On<UniqueFinalized>, mut finalized: ResMut<Finalized>| {
+				finalized.0 += 1;
+			})
+			.add_observer(|event: On<ModsFailed>, mut failed: ResMut<Failed>| {
+				failed.0.push(event.ids.clone());
+			});
+		let content = |id: &str, dependencies: &[&str]| ModContent {
+			manifest: ModManifest {
+				id: SmolStr::new(id),
+				dependencies: dependencies.iter().map(SmolStr::new).collect(),
+				..Default::default()
+			},
+			prototypes: Vec::new(),
+		};
+		app.world_mut()
+			.resource_mut::<PendingMods>()
+			.push(content("patch", &["base"]));
+
+		for _ in 0..3 {
+			app.update();
+		}
+		let world = app.world_mut();
+		assert_eq!(*world.resource::<UniquePhase>(), UniquePhase::Frozen);
+		assert_eq!(world.resource::<Finalized>().0, 1);
+		assert_eq!(world.resource::<Failed>().0, [["patch"]]);
+		assert!(world.resource::<PendingMods>().is_empty());
+		assert_eq!(world.resource::<FailedMods>().len(), 1);
+
+		world
+			.resource_mut::<PendingMods>()
+			.push(content("extra", &["not_installed"]));
+		for _ in 0..3 {
+			app.update();
+		}
+		let world = app.world();
+		assert_eq!(world.resource::<Failed>().0, [["patch"], ["extra"]]);
+		assert_eq!(world.resource::<FailedMods>().len(), 2);
+		assert!(world.resource::<ModLoadOrder>().is_empty());
+	}
+}
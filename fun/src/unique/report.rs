@@ -0,0 +1,160 @@
+use std::collections::BTreeMap;
+
+use bevy_derive::*;
+use bevy_ecs::{component::Components, prelude::*};
+use bevy_reflect::Reflect;
+use smol_str::{SmolStr, ToSmolStr};
+
+use super::*;
+
+/// Where the prototype come from, inserted by [spawn_prototype].
+#[derive(Component, Reflect, Clone, Debug, Default, PartialEq)]
+#[reflect(Component)]
+pub struct PrototypeSource {
+	pub mod_id: SmolStr,
+	pub file: SmolStr,
+}
+
+impl PrototypeSource {
+	/// Spawned without source use the entity instead.
+	pub fn label(source: Option<&Self>, entity: Entity) -> SmolStr {
+		match source {
+			Some(source) if source.file.is_empty() => source.mod_id.clone(),
+			Some(source) => smol_str::format_smolstr!("{}/{}", source.mod_id, source.file),
+			None => entity.to_smolstr(),
+		}
+	}
+}
+
+/// Which source contributed every component of every [UniqueName] and what it replaced.
+///
+/// Dump with [MoreReflect](crate::serding::MoreReflect):
+/// `registry.save_assets_ron(report.as_partial_reflect(), path)`
+#[derive(Resource, Reflect, Default, Debug, Deref)]
+#[reflect(Resource)]
+pub struct UniqueReport(BTreeMap<SmolStr, UniqueRecord>);
+
+/// Component type path to its record.
+#[derive(Reflect, Default, Debug, Clone, Deref)]
+pub struct UniqueRecord(BTreeMap<SmolStr, ComponentRecord>);
+
+#[derive(Reflect, Debug, Clone, PartialEq)]
+pub struct ComponentRecord {
+	/// Source in effect.
+	pub source: SmolStr,
+	/// Replaced sources, oldest first.
+	pub overridden: Vec<SmolStr>,
+}
+
+impl UniqueReport {
+	pub fn get_record(&self, name: &str) -> Option<&UniqueRecord> {
+		self.0.get(name)
+	}
+
+	/// Name, component and record of every component replaced at least once.
+	pub fn overridden(&self) -> impl Iterator<Item = (&SmolStr, &SmolStr, &ComponentRecord)> {
+		self.0.iter().flat_map(|(name, record)| {
+			record
+				.iter()
+				.filter(|(_, component)| !component.overridden.is_empty())
+				.map(move |(component_name, component)| (name, component_name, component))
+		})
+	}
+
+	pub(super) fn contribute(&mut self, name: &UniqueName, source: &SmolStr, component_names: Vec<SmolStr>) {
+		let record = self.0.entry(name.0.clone()).or_default();
+		for component_name in component_names {
+			match record.0.get_mut(&component_name) {
+				Some(component) => {
+					let previous = std::mem::replace(&mut component.source, source.clone());
+					component.overridden.push(previous);
+				}
+				None => {
+					record.0.insert(
+						component_name,
+						ComponentRecord {
+							source: source.clone(),
+							overridden: Vec::new(),
+						},
+					);
+				}
+			}
+		}
+	}
+
+	pub(super) fn remove(&mut self, name: &UniqueName) {
+		self.0.remove(&name.0);
+	}
+}
+
+/// Source label and name of every component not used by unique itself.
+pub(super) fn contribution(ent_ref: EntityRef, components: &Components) -> (SmolStr, Vec<SmolStr>) {
+	let source = PrototypeSource::label(ent_ref.get::<PrototypeSource>(), ent_ref.id());
+	let component_names = ent_ref
+		.archetype()
+		.iter_components()
+		.filter_map(|component_id| components.get_info(component_id))
+		.filter(|info| info.type_id().is_none_or(|type_id| !is_unique_internal(type_id)))
+		.map(|info| info.name().to_smolstr())
+		.collect();
+	(source, component_names)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::serding::MoreReflect;
+	use bevy_reflect::PartialReflect;
+	use bevy_utils::prelude::DebugName;
+
+	#[derive(Component)]
+	struct A(u8);
+	#[derive(Component)]
+	struct B(u8);
+
+	#[test]
+	fn test_report() {
+		let mut app = App::new();
+		app.add_plugins(UniquePlugin)
+			.add_systems(Startup, setup)
+			.add_systems(Last, checkout);
+
+		app.run();
+	}
+
+	fn setup(world: &mut World) {
+		let source = |mod_id: &str| PrototypeSource {
+			mod_id: SmolStr::new(mod_id),
+			file: SmolStr::new("items.ron"),
+		};
+		world.spawn((source("base"), A(1), B(1), UniqueName::new("Test_1")));
+		world.spawn((source("patch"), A(2), UniqueName::new("Test_1")));
+		world.spawn((source("last"), A(3), UniqueName::new("Test_1")));
+		world.spawn((source("base"), B(1), UniqueName::new("Test_2")));
+	}
+
+	fn checkout(world: &mut World) {
+		let report = world.resource::<UniqueReport>();
+		let a_name = DebugName::type_name::<A>().to_smolstr();
+		let b_name = DebugName::type_name::<B>().to_smolstr();
+
+		let test_1 = report.get_record("Test_1").unwrap();
+		assert_eq!(
+			test_1[&a_name],
+			ComponentRecord {
+				source: SmolStr::new("last/items.ron"),
+				overridden: vec![SmolStr::new("base/items.ron"), SmolStr::new("patch/items.ron")],
+			}
+		);
+		assert!(test_1[&b_name].overridden.is_empty());
+		assert_eq!(report.overridden().count(), 1);
+		assert_eq!(report.len(), 2);
+
+		let mut path = std::env::temp_dir();
+		path.push("unique_report_test");
+		let registry = world.resource::<AppTypeRegistry>();
+		registry.save_assets_ron(report.as_partial_reflect(), path.clone());
+		let loaded = registry.read_into_typed_ron::<UniqueReport>(path).unwrap();
+		assert_eq!(loaded.get_record("Test_1").unwrap()[&a_name], test_1[&a_name]);
+	}
+}
@@ -0,0 +1,478 @@
+use std::{any::TypeId, fmt, path::PathBuf};
+
+use bevy_ecs::prelude::*;
+use bevy_log::prelude::*;
+use bevy_reflect::{PartialReflect, TypeRegistry, serde::TypedReflectSerializer};
+use serde::{
+	Serialize, Serializer,
+	de::{DeserializeSeed, Deserializer, Error as _, IgnoredAny, MapAccess, SeqAccess, Visitor},
+	ser::{SerializeSeq, SerializeStruct},
+};
+use smol_str::SmolStr;
+
+use super::*;
+use crate::serding::ReflectMapSerializer;
+
+/// Instance saved as its prototype name and only what differ from it.
+///
+/// ```ron
+/// [
+///     (
+///         prototype: "Sword",
+///         fingerprint: Some((1234)),
+///         sync: true,
+///         args: ({
+///             "level": Int(12),
+///         }),
+///         removed: ["game::Weight"],
+///         changed: {
+///             "game::Damage": (99),
+///         },
+///     ),
+/// ]
+/// ```
+#[derive(Debug)]
+pub struct InstanceDelta {
+	pub prototype: UniqueName,
+	/// Of the prototype when saved, checked on load.
+	pub fingerprint: Option<UniqueFingerprint>,
+	/// Spawn back with [PrototypeSync].
+	pub sync: bool,
+	/// Spawned with, prototype components compared after substitution.
+	pub args: PrototypeArgs,
+	/// Type path of prototype component missing on the instance.
+	pub removed: Vec<SmolStr>,
+	/// Component not on the prototype or not equal to it.
+	pub changed: Vec<Box<dyn PartialReflect>>,
+}
+
+impl InstanceDelta {
+	/// [None] if not an [InstanceOf] or the prototype is gone.
+	pub fn compute(world: &World, registry: &TypeRegistry, entity: Entity) -> Option<Self> {
+		let prototype = world.get::<InstanceOf>(entity)?.0;
+		let name = world.get::<UniqueName>(prototype)?.clone();
+
+		let args = world.get::<PrototypeArgs>(entity).cloned().unwrap_or_default();
+		let from_prototype = instance_components(world, registry, prototype, &args);
+		let mut current = snapshot_components(world, registry, entity);
+		current.retain(|component| {
+			component
+				.get_represented_type_info()
+				.is_none_or(|type_info| type_info.type_id() != TypeId::of::<PrototypeArgs>())
+		});
+		let type_path = |component: &dyn PartialReflect| {
+			component
+				.get_represented_type_info()
+				.map(|type_info| type_info.type_path())
+		};
+
+		let removed = from_prototype
+			.iter()
+			.filter_map(|component| type_path(component.as_ref()))
+			.filter(|path| {
+				!current
+					.iter()
+					.any(|component| type_path(component.as_ref()) == Some(path))
+			})
+			.map(SmolStr::new)
+			.collect();
+
+		let changed = current
+			.into_iter()
+			.filter(|component| {
+				let same = from_prototype
+					.iter()
+					.find(|original| type_path(original.as_ref()) == type_path(component.as_ref()))
+					.is_some_and(|original| original.reflect_partial_eq(component.as_ref()) == Some(true));
+				!same
+			})
+			.collect();
+
+		Some(Self {
+			prototype: name,
+			fingerprint: world.get::<UniqueFingerprint>(prototype).copied(),
+			sync: world.entity(entity).contains::<PrototypeSync>(),
+			args,
+			removed,
+			changed,
+		})
+	}
+
+	/// [None] if saved without [UniqueFingerprint].
+	pub fn fingerprint_status(&self, world: &World) -> Option<FingerprintStatus> {
+		Some(fingerprint_status(world, &self.prototype.0, self.fingerprint?))
+	}
+
+	/// Spawn with [InstanceFrom] then reapply the delta, nothing spawned if the prototype is missing.
+	pub fn spawn(&self, world: &mut World, registry: &TypeRegistry) -> Result<Entity> {
+		if world.resource::<UniqueHashed>().get_ent(&self.prototype.0).is_none() {
+			return Err(format!("Instance of missing prototype {}", self.prototype.0).into());
+		}
+		let entity = world.spawn_empty().id();
+		InstanceFrom {
+			name: self.prototype.clone(),
+			sync: self.sync,
+			args: self.args.clone(),
+		}
+		.apply(world.entity_mut(entity));
+
+		let mut ent_mut = world.entity_mut(entity);
+
+		for type_path in &self.removed {
+			match registry
+				.get_with_type_path(type_path)
+				.and_then(|registration| registration.data::<ReflectComponent>())
+			{
+				Some(reflect_component) => reflect_component.remove(&mut ent_mut),
+				None => warn!("Instance delta has unregistered component: {}", type_path),
+			}
+		}
+		insert_layer(&mut ent_mut, registry, &self.changed);
+		Ok(ent_mut.id())
+	}
+}
+
+/// Save every [InstanceOf] entity as [InstanceDelta], missing directory created.
+pub fn save_instances_ron(world: &mut World, mut path: PathBuf) -> Result {
+	path.set_extension("ron");
+	let registry = world.resource::<AppTypeRegistry>().clone();
+	let registry = registry.read();
+	let mut q_instance = world.query_filtered::<Entity, With<InstanceOf>>();
+	let deltas = q_instance
+		.iter(world)
+		.filter_map(|entity| InstanceDelta::compute(world, &registry, entity))
+		.collect::<Vec<_>>();
+
+	let serializer = DeltasSerializer(&deltas, &registry);
+	let content = ron::ser::to_string_pretty(&serializer, ron::ser::PrettyConfig::new())?;
+	if let Some(parent) = path.parent() {
+		std::fs::create_dir_all(parent)?;
+	}
+	std::fs::write(&path, content)?;
+	Ok(())
+}
+
+pub fn read_instances_ron(world: &mut World, mut path: PathBuf) -> Result<Vec<Entity>> {
+	path.set_extension("ron");
+	let data = std::fs::read(path)?;
+	into_instances_ron(world, &data)
+}
+
+/// Spawn every saved [InstanceDelta], prototype must be in [UniqueHashed] already.
+///
+/// Error without spawning anything if a prototype is missing.
+/// Warn for prototype changed since saved, see [InstanceDelta::fingerprint_status].
+pub fn into_instances_ron(world: &mut World, data: &[u8]) -> Result<Vec<Entity>> {
+	let registry = world.resource::<AppTypeRegistry>().clone();
+	let registry = registry.read();
+	let mut deserializer = ron::Deserializer::from_bytes(data)?;
+	let deltas = DeltasSeed(&registry).deserialize(&mut deserializer)?;
+	let hashed = world.resource::<UniqueHashed>();
+	let mut missing = deltas
+		.iter()
+		.filter(|delta| hashed.get_ent(&delta.prototype.0).is_none())
+		.map(|delta| delta.prototype.0.clone())
+		.collect::<Vec<_>>();
+	if !missing.is_empty() {
+		missing.dedup();
+		return Err(format!("Instances of missing prototypes {:?}", missing).into());
+	}
+	for delta in deltas.iter() {
+		if delta.fingerprint_status(world) == Some(FingerprintStatus::Changed) {
+			warn!("Prototype {} changed since saved", delta.prototype.0);
+		}
+	}
+	deltas.iter().map(|delta| delta.spawn(world, &registry)).collect()
+}
+
+struct DeltasSerializer<'a>(&'a [InstanceDelta], &'a TypeRegistry);
+
+impl<'a> Serialize for DeltasSerializer<'a> {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		let mut seq = serializer.serialize_seq(Some(self.0.len()))?;
+		for delta in self.0 {
+			seq.serialize_element(&DeltaSerializer(delta, self.1))?;
+		}
+		seq.end()
+	}
+}
+
+struct DeltaSerializer<'a>(&'a InstanceDelta, &'a TypeRegistry);
+
+impl<'a> Serialize for DeltaSerializer<'a> {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		let mut state = serializer.serialize_struct("InstanceDelta", 6)?;
+		state.serialize_field("prototype", self.0.prototype.0.as_str())?;
+		state.serialize_field("fingerprint", &self.0.fingerprint)?;
+		state.serialize_field("sync", &self.0.sync)?;
+		state.serialize_field("args", &self.0.args)?;
+		state.serialize_field("removed", &self.0.removed)?;
+		state.serialize_field("changed", &ReflectMapSerializer(&self.0.changed, self.1))?;
+		state.end()
+	}
+}
+
+struct DeltasSeed<'a>(&'a TypeRegistry);
+
+impl<'a, 'de> DeserializeSeed<'de> for DeltasSeed<'a> {
+	type Value = Vec<InstanceDelta>;
+
+	fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+		deserializer.deserialize_seq(self)
+	}
+}
+
+impl<'a, 'de> Visitor<'de> for DeltasSeed<'a> {
+	type Value = Vec<InstanceDelta>;
+
+	fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+		formatter.write_str("list of instance delta")
+	}
+
+	fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+		let mut deltas = Vec::new();
+		while let Some(delta) = seq.next_element_seed(DeltaSeed(self.0))? {
+			deltas.push(delta);
+		}
+		Ok(deltas)
+	}
+}
+
+struct DeltaSeed<'a>(&'a TypeRegistry);
+
+impl<'a, 'de> DeserializeSeed<'de> for DeltaSeed<'a> {
+	type Value = InstanceDelta;
+
+	fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+		deserializer.deserialize_struct(
+			"InstanceDelta",
+			&["prototype", "fingerprint", "sync", "args", "removed", "changed"],
+			self,
+		)
+	}
+}
+
+impl<'a, 'de> Visitor<'de> for DeltaSeed<'a> {
+	type Value = InstanceDelta;
+
+	fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+		formatter.write_str("instance delta")
+	}
+
+	fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+		let mut prototype = None;
+		let mut fingerprint = None;
+		let mut sync = false;
+		let mut args = PrototypeArgs::default();
+		let mut removed = Vec::new();
+		let mut changed = Vec::new();
+		while let Some(key) = map.next_key::<String>()? {
+			match key.as_str() {
+				"prototype" => prototype = Some(UniqueName::new(&map.next_value::<String>()?)),
+				"fingerprint" => fingerprint = map.next_value()?,
+				"sync" => sync = map.next_value()?,
+				"args" => args = map.next_value()?,
+				"removed" => removed = map.next_value()?,
+				"changed" => changed = map.next_value_seed(ComponentsSeed(self.0))?,
+				_ => {
+					map.next_value::<IgnoredAny>()?;
+				}
+			}
+		}
+		Ok(InstanceDelta {
+			prototype: prototype.ok_or_else(|| A::Error::missing_field("prototype"))?,
+			fingerprint,
+			sync,
+			args,
+			removed,
+			changed,
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use bevy_reflect::Reflect;
+
+	#[derive(Component, Reflect, Default)]
+	#[reflect(Component)]
+	struct Damage(u32);
+
+	#[derive(Component, Reflect, Default)]
+	#[reflect(Component)]
+	struct Weight(u32);
+
+	#[derive(Component, Reflect, Default)]
+	#[reflect(Component)]
+	struct Owner(String);
+
+	#[derive(Component, Reflect, Default)]
+	#[reflect(Component)]
+	struct Level(u8);
+
+	#[test]
+	fn test_instance_delta() {
+		let mut app = App::new();
+		app.add_plugins(UniquePlugin)
+			.register_type::<Damage>()
+			.register_type::<Weight>()
+			.register_type::<Owner>()
+			.add_systems(PreStartup, setup)
+			.add_systems(Startup, spawn_instances)
+			.add_systems(Last, save_and_load);
+
+		app.run();
+	}
+
+	fn setup(world: &mut World) {
+		world.spawn((Damage(10), Weight(3), UniqueName::new("Sword")));
+	}
+
+	fn spawn_instances(mut cmd: Commands) {
+		cmd.spawn_empty().queue(InstanceFrom::new("Sword", true));
+		cmd.spawn_empty()
+			.queue(InstanceFrom::new("Sword", false))
+			.insert((Damage(99), Owner(String::from("Chssam"))))
+			.remove::<Weight>();
+	}
+
+	fn save_and_load(world: &mut World) {
+		let registry = world.resource::<AppTypeRegistry>().clone();
+		let mut q_instance = world.query_filtered::<Entity, With<InstanceOf>>();
+		let deltas = q_instance
+			.iter(world)
+			.filter_map(|entity| InstanceDelta::compute(world, &registry.read(), entity))
+			.collect::<Vec<_>>();
+
+		let untouched = deltas.iter().find(|delta| delta.sync).unwrap();
+		assert!(untouched.removed.is_empty() && untouched.changed.is_empty());
+
+		let modified = deltas.iter().find(|delta| !delta.sync).unwrap();
+		assert_eq!(modified.removed.len(), 1);
+		assert_eq!(modified.changed.len(), 2);
+
+		let saved = ron::ser::to_string(&DeltasSerializer(&deltas, &registry.read())).unwrap();
+		q_instance
+			.iter(world)
+			.collect::<Vec<_>>()
+			.into_iter()
+			.for_each(|entity| {
+				world.despawn(entity);
+			});
+
+		let loaded = into_instances_ron(world, saved.as_bytes()).unwrap();
+		assert_eq!(loaded.len(), 2);
+		let (synced, modified) = match world.entity(loaded[0]).contains::<PrototypeSync>() {
+			true => (loaded[0], loaded[1]),
+			false => (loaded[1], loaded[0]),
+		};
+
+		assert_eq!(world.get::<Damage>(synced).unwrap().0, 10);
+		assert_eq!(world.get::<Weight>(synced).unwrap().0, 3);
+		assert_eq!(world.get::<Damage>(modified).unwrap().0, 99);
+		assert_eq!(world.get::<Owner>(modified).unwrap().0, "Chssam");
+		assert!(world.get::<Weight>(modified).is_none());
+
+		// Through file, directory not existing yet
+		let mut dir = std::env::temp_dir();
+		dir.push(format!("unique_instance_test_{}", std::process::id()));
+		let mut path = dir.clone();
+		path.push("saves");
+		path.push("instances");
+		save_instances_ron(world, path.clone()).unwrap();
+		let reloaded = read_instances_ron(world, path).unwrap();
+		std::fs::remove_dir_all(dir).unwrap();
+
+		assert_eq!(reloaded.len(), 2);
+		let owners = reloaded
+			.iter()
+			.filter_map(|entity| world.get::<Owner>(*entity))
+			.collect::<Vec<_>>();
+		assert_eq!(owners.len(), 1);
+		assert_eq!(owners[0].0, "Chssam");
+	}
+
+	#[test]
+	fn test_instance_delta_args() {
+		let mut app = App::new();
+		app.add_plugins(UniquePlugin)
+			.register_type::<Level>()
+			.add_systems(PreStartup, setup_params)
+			.add_systems(Startup, spawn_leveled)
+			.add_systems(Last, save_and_load_leveled);
+
+		app.run();
+	}
+
+	fn setup_params(world: &mut World) {
+		let params = PrototypeParams(vec![PrototypeParam {
+			name: SmolStr::new("level"),
+			component: SmolStr::new("reflection_fun::unique::save::tests::Level"),
+			path: SmolStr::new(".0"),
+			default: ParamValue::Int(1),
+		}]);
+		world.spawn((params, Level(0), UniqueName::new("Goblin")));
+	}
+
+	fn spawn_leveled(mut cmd: Commands) {
+		cmd.spawn_empty()
+			.queue(InstanceFrom::new("Goblin", true).with_arg("level", ParamValue::Int(12)));
+	}
+
+	fn save_and_load_leveled(world: &mut World) {
+		let registry = world.resource::<AppTypeRegistry>().clone();
+		let mut q_instance = world.query_filtered::<Entity, With<InstanceOf>>();
+		let instance = q_instance.single(world).unwrap();
+
+		// Level(12) come from the args, not a local change
+		let delta = InstanceDelta::compute(world, &registry.read(), instance).unwrap();
+		assert_eq!(delta.args.get("level"), Some(&ParamValue::Int(12)));
+		assert!(delta.removed.is_empty() && delta.changed.is_empty());
+
+		let saved = ron::ser::to_string(&DeltasSerializer(&[delta], &registry.read())).unwrap();
+		world.despawn(instance);
+
+		let loaded = into_instances_ron(world, saved.as_bytes()).unwrap();
+		assert_eq!(world.get::<Level>(loaded[0]).unwrap().0, 12);
+		assert_eq!(
+			world.get::<PrototypeArgs>(loaded[0]).unwrap().get("level"),
+			Some(&ParamValue::Int(12))
+		);
+
+		// Still synced with the args substituted
+		let goblin = world.resource::<UniqueHashed>().get_ent("Goblin").unwrap();
+		world.entity_mut(goblin).insert(Level(5));
+		propagate_to_instances(world, goblin);
+		assert_eq!(world.get::<Level>(loaded[0]).unwrap().0, 12);
+	}
+
+	/// No orphan entity, even for the instance with its prototype
+	#[test]
+	fn test_instance_delta_missing() {
+		let mut app = App::new();
+		app.add_plugins(UniquePlugin).register_type::<Damage>();
+		let world = app.world_mut();
+		world.spawn((Damage(10), UniqueName::new("Sword")));
+		world.flush();
+
+		let saved = r#"[(prototype: "Sword"), (prototype: "Dragon"), (prototype: "Dragon")]"#;
+		let before = world.entities().count_spawned();
+		let err = into_instances_ron(world, saved.as_bytes()).unwrap_err();
+		assert!(err.to_string().contains("[\"Dragon\"]"));
+		assert_eq!(world.entities().count_spawned(), before);
+		assert!(world.query::<&InstanceOf>().iter(world).next().is_none());
+
+		let delta = InstanceDelta {
+			prototype: UniqueName::new("Dragon"),
+			fingerprint: None,
+			sync: false,
+			args: PrototypeArgs::default(),
+			removed: Vec::new(),
+			changed: Vec::new(),
+		};
+		let registry = world.resource::<AppTypeRegistry>().clone();
+		assert!(delta.spawn(world, &registry.read()).is_err());
+		assert_eq!(world.entities().count_spawned(), before);
+	}
+}
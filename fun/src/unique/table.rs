@@ -0,0 +1,292 @@
+use std::{collections::BTreeMap, fmt};
+
+use bevy_derive::*;
+use bevy_ecs::prelude::*;
+use bevy_reflect::Reflect;
+use serde::{Deserialize, Serialize};
+use smol_str::SmolStr;
+
+use super::*;
+
+/// Loot, spawn or encounter table, rolled into [UniqueName] with [TableRng].
+///
+/// ```ron
+/// (
+///     rolls: (min: 1, max: 2),
+///     guaranteed: [(entry: Name("core:coin"), count: (min: 5, max: 10))],
+///     entries: [
+///         (entry: Tag("weapon"), weight: 3),
+///         (entry: Name("core:potion"), weight: 1, chance: 0.5),
+///         (entry: Table((entries: [(entry: Name("core:gem"))]))),
+///         (entry: Nothing, weight: 6),
+///     ],
+/// )
+/// ```
+#[derive(Reflect, Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(default)]
+pub struct RandomTable {
+	/// How many pick from [RandomTable::entries].
+	pub rolls: CountRange,
+	/// Every drop rolled once, weight ignored.
+	pub guaranteed: Vec<TableDrop>,
+	/// Picked by weight.
+	pub entries: Vec<TableDrop>,
+}
+
+#[derive(Reflect, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct TableDrop {
+	pub entry: TableEntry,
+	pub weight: u32,
+	pub count: CountRange,
+	/// Optional drop, rolled after picked.
+	pub chance: f32,
+}
+
+impl Default for TableDrop {
+	fn default() -> Self {
+		Self {
+			entry: TableEntry::Nothing,
+			weight: 1,
+			count: CountRange::default(),
+			chance: 1.0,
+		}
+	}
+}
+
+#[derive(Reflect, Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub enum TableEntry {
+	#[default]
+	Nothing,
+	Name(SmolStr),
+	/// Any name with the tag in [UniqueIndex], evenly.
+	Tag(SmolStr),
+	Table(RandomTable),
+}
+
+/// Inclusive.
+#[derive(Reflect, Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CountRange {
+	pub min: u32,
+	pub max: u32,
+}
+
+impl Default for CountRange {
+	fn default() -> Self {
+		Self { min: 1, max: 1 }
+	}
+}
+
+/// SplitMix64, same seed give same roll on every platform and version.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TableRng(pub u64);
+
+impl TableRng {
+	pub fn next_u64(&mut self) -> u64 {
+		self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+		let mut z = self.0;
+		z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+		z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+		z ^ (z >> 31)
+	}
+
+	/// Below `bound`, `0` if `bound` is `0`.
+	pub fn below(&mut self, bound: u64) -> u64 {
+		match bound {
+			0 => 0,
+			_ => self.next_u64() % bound,
+		}
+	}
+
+	pub fn range(&mut self, range: CountRange) -> u32 {
+		let span = range.max.saturating_sub(range.min) as u64 + 1;
+		range.min + self.below(span) as u32
+	}
+
+	pub fn chance(&mut self, chance: f32) -> bool {
+		chance >= 1.0 || ((self.next_u64() >> 40) as f32 / (1u64 << 24) as f32) < chance
+	}
+}
+
+/// Named tables, read from RON map of name to [RandomTable].
+#[derive(Resource, Default, Debug, Deref, DerefMut)]
+pub struct RandomTables(pub BTreeMap<SmolStr, RandomTable>);
+
+impl RandomTables {
+	pub fn read_ron(data: &[u8]) -> Result<Self> {
+		Ok(Self(ron::de::from_bytes(data)?))
+	}
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum TableError {
+	UnknownName(SmolStr),
+	UnknownTag(SmolStr),
+	InvalidCount(CountRange),
+	/// Entries but every weight is `0`.
+	NoWeight,
+}
+
+impl fmt::Display for TableError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			TableError::UnknownName(name) => write!(f, "Unknown unique {}", name),
+			TableError::UnknownTag(tag) => write!(f, "No unique tagged {}", tag),
+			TableError::InvalidCount(range) => write!(f, "Count min {} above max {}", range.min, range.max),
+			TableError::NoWeight => write!(f, "Every entry weight is 0"),
+		}
+	}
+}
+
+impl RandomTable {
+	/// Names may repeat, one per count.
+	pub fn roll(&self, rng: &mut TableRng, index: &UniqueIndex) -> Vec<UniqueName> {
+		let mut rolled = Vec::new();
+		self.roll_into(rng, index, &mut rolled);
+		rolled
+	}
+
+	fn roll_into(&self, rng: &mut TableRng, index: &UniqueIndex, rolled: &mut Vec<UniqueName>) {
+		for drop in &self.guaranteed {
+			drop.roll_into(rng, index, rolled);
+		}
+
+		let total = self.entries.iter().map(|drop| drop.weight as u64).sum::<u64>();
+		if total == 0 {
+			return;
+		}
+		for _ in 0..rng.range(self.rolls) {
+			let mut pick = rng.below(total);
+			let Some(drop) = self.entries.iter().find(|drop| {
+				let found = pick < drop.weight as u64;
+				pick = pick.saturating_sub(drop.weight as u64);
+				found
+			}) else {
+				continue;
+			};
+			drop.roll_into(rng, index, rolled);
+		}
+	}
+
+	/// Every referenced name and tag must exist, including nested table.
+	pub fn validate(&self, hashed: &UniqueHashed, index: &UniqueIndex) -> Vec<TableError> {
+		let mut errors = Vec::new();
+		self.validate_into(hashed, index, &mut errors);
+		errors
+	}
+
+	fn validate_into(&self, hashed: &UniqueHashed, index: &UniqueIndex, errors: &mut Vec<TableError>) {
+		if self.rolls.min > self.rolls.max {
+			errors.push(TableError::InvalidCount(self.rolls));
+		}
+		if !self.entries.is_empty() && self.entries.iter().all(|drop| drop.weight == 0) {
+			errors.push(TableError::NoWeight);
+		}
+		for drop in self.guaranteed.iter().chain(&self.entries) {
+			if drop.count.min > drop.count.max {
+				errors.push(TableError::InvalidCount(drop.count));
+			}
+			match &drop.entry {
+				TableEntry::Nothing => {}
+				TableEntry::Name(name) => {
+					if hashed.get_ent(name).is_none() {
+						errors.push(TableError::UnknownName(name.clone()));
+					}
+				}
+				TableEntry::Tag(tag) => {
+					if index.tagged(tag).next().is_none() {
+						errors.push(TableError::UnknownTag(tag.clone()));
+					}
+				}
+				TableEntry::Table(table) => table.validate_into(hashed, index, errors),
+			}
+		}
+	}
+}
+
+impl TableDrop {
+	fn roll_into(&self, rng: &mut TableRng, index: &UniqueIndex, rolled: &mut Vec<UniqueName>) {
+		if !rng.chance(self.chance) {
+			return;
+		}
+		for _ in 0..rng.range(self.count) {
+			match &self.entry {
+				TableEntry::Nothing => {}
+				TableEntry::Name(name) => rolled.push(UniqueName(name.clone())),
+				TableEntry::Tag(tag) => {
+					let tagged = index.tagged(tag).collect::<Vec<_>>();
+					if let Some(name) = tagged.get(rng.below(tagged.len() as u64) as usize) {
+						rolled.push(UniqueName((*name).clone()));
+					}
+				}
+				TableEntry::Table(table) => table.roll_into(rng, index, rolled),
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_table() {
+		let mut app = App::new();
+		app.add_plugins(UniquePlugin)
+			.add_systems(Startup, setup)
+			.add_systems(Last, checkout);
+
+		app.run();
+	}
+
+	fn setup(world: &mut World) {
+		world.spawn(UniqueName::new("core:coin"));
+		world.spawn(UniqueName::new("core:gem"));
+		world.spawn((UniqueTags::new(&["weapon"]), UniqueName::new("core:sword")));
+		world.spawn((UniqueTags::new(&["weapon"]), UniqueName::new("core:axe")));
+	}
+
+	fn checkout(world: &mut World) {
+		let tables = RandomTables::read_ron(
+			br#"{
+				"chest": (
+					rolls: (min: 2, max: 4),
+					guaranteed: [(entry: Name("core:coin"), count: (min: 5, max: 10))],
+					entries: [
+						(entry: Tag("weapon"), weight: 3),
+						(entry: Table((entries: [(entry: Name("core:gem"))]))),
+						(entry: Nothing, weight: 2),
+					],
+				),
+				"broken": (
+					rolls: (min: 2, max: 1),
+					guaranteed: [(entry: Name("core:missing")), (entry: Tag("armor"), chance: 0.5)],
+				),
+			}"#,
+		)
+		.unwrap();
+		let hashed = world.resource::<UniqueHashed>();
+		let index = world.resource::<UniqueIndex>();
+
+		let chest = &tables["chest"];
+		assert!(chest.validate(hashed, index).is_empty());
+		assert_eq!(
+			tables["broken"].validate(hashed, index),
+			[
+				TableError::InvalidCount(CountRange { min: 2, max: 1 }),
+				TableError::UnknownName(SmolStr::new("core:missing")),
+				TableError::UnknownTag(SmolStr::new("armor")),
+			]
+		);
+
+		for seed in 0..32 {
+			let rolled = chest.roll(&mut TableRng(seed), index);
+			assert_eq!(rolled, chest.roll(&mut TableRng(seed), index));
+
+			let coins = rolled.iter().filter(|name| name.0 == "core:coin").count();
+			assert!((5..=10).contains(&coins));
+			assert!((5..=14).contains(&rolled.len()));
+			assert!(rolled.iter().all(|name| hashed.contains_key(name)));
+		}
+	}
+}
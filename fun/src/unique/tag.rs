@@ -0,0 +1,196 @@
+use std::{
+	collections::{BTreeMap, BTreeSet},
+	ops::Bound,
+};
+
+use bevy_derive::*;
+use bevy_ecs::{lifecycle::HookContext, prelude::*, world::DeferredWorld};
+use bevy_reflect::Reflect;
+use smol_str::SmolStr;
+
+use super::*;
+
+/// Tags of the prototype, only the [UniqueHashed] entity is indexed in [UniqueIndex].
+#[derive(Component, Reflect, Clone, Debug, Default, PartialEq, Deref)]
+#[reflect(Component)]
+#[component(on_insert = UniqueTags::on_insert, on_replace = UniqueTags::on_replace)]
+pub struct UniqueTags(pub Vec<SmolStr>);
+
+impl UniqueTags {
+	pub fn new(tags: &[&str]) -> Self {
+		Self(tags.iter().map(SmolStr::new).collect())
+	}
+
+	fn hashed_name(world: &DeferredWorld, entity: Entity) -> Option<UniqueName> {
+		let name = world.entity(entity).get::<UniqueName>()?;
+		let hashed = world.get_resource::<UniqueHashed>()?;
+		(hashed.get(name) == Some(&entity)).then(|| name.clone())
+	}
+
+	fn on_insert(mut world: DeferredWorld, HookContext { entity, .. }: HookContext) {
+		let Some(name) = Self::hashed_name(&world, entity) else {
+			return;
+		};
+		let tags = world.entity(entity).get::<UniqueTags>().cloned().unwrap_or_default();
+		world.resource_mut::<UniqueIndex>().insert_tags(&name, &tags);
+	}
+
+	fn on_replace(mut world: DeferredWorld, HookContext { entity, .. }: HookContext) {
+		let Some(name) = Self::hashed_name(&world, entity) else {
+			return;
+		};
+		let tags = world.entity(entity).get::<UniqueTags>().cloned().unwrap_or_default();
+		let mut index = world.resource_mut::<UniqueIndex>();
+		for tag in tags.0 {
+			if let Some(names) = index.tags.get_mut(&tag) {
+				names.remove(&name.0);
+				if names.is_empty() {
+					index.tags.remove(&tag);
+				}
+			}
+		}
+	}
+}
+
+/// Every [UniqueHashed] name and [UniqueTags], iterate in name order.
+#[derive(Resource, Default, Debug)]
+pub struct UniqueIndex {
+	names: BTreeSet<SmolStr>,
+	tags: BTreeMap<SmolStr, BTreeSet<SmolStr>>,
+}
+
+impl UniqueIndex {
+	pub fn names(&self) -> impl Iterator<Item = &SmolStr> {
+		self.names.iter()
+	}
+
+	/// `"items/"` match `"items/sword"` and `"items/shield"`.
+	pub fn with_prefix<'a>(&'a self, prefix: &'a str) -> impl Iterator<Item = &'a SmolStr> {
+		self.names
+			.range::<str, _>((Bound::Included(prefix), Bound::Unbounded))
+			.take_while(move |name| name.starts_with(prefix))
+	}
+
+	pub fn tags(&self) -> impl Iterator<Item = &SmolStr> {
+		self.tags.keys()
+	}
+
+	pub fn tagged(&self, tag: &str) -> impl Iterator<Item = &SmolStr> {
+		self.tags.get(tag).into_iter().flatten()
+	}
+
+	/// Names having every tag.
+	pub fn tagged_all(&self, tags: &[&str]) -> Vec<&SmolStr> {
+		let Some((first, rest)) = tags.split_first() else {
+			return Vec::new();
+		};
+		self.tagged(first)
+			.filter(|name| {
+				rest.iter()
+					.all(|tag| self.tags.get(*tag).is_some_and(|names| names.contains(*name)))
+			})
+			.collect()
+	}
+
+	/// Names having at least one tag.
+	pub fn tagged_any(&self, tags: &[&str]) -> Vec<&SmolStr> {
+		tags.iter()
+			.flat_map(|tag| self.tagged(tag))
+			.collect::<BTreeSet<_>>()
+			.into_iter()
+			.collect()
+	}
+
+	pub(super) fn insert_name(&mut self, name: &UniqueName) {
+		self.names.insert(name.0.clone());
+	}
+
+	/// Also by [UniqueEntity] once registered, tags may be inserted before the name.
+	pub(super) fn insert_tags(&mut self, name: &UniqueName, tags: &UniqueTags) {
+		for tag in tags.iter() {
+			self.tags.entry(tag.clone()).or_default().insert(name.0.clone());
+		}
+	}
+
+	pub(super) fn remove_name(&mut self, name: &UniqueName) {
+		self.names.remove(&name.0);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use bevy_reflect::PartialReflect;
+
+	#[test]
+	fn test_index() {
+		let mut app = App::new();
+		app.add_plugins(UniquePlugin)
+			.add_systems(PreStartup, setup)
+			.add_systems(Startup, check_index)
+			.add_systems(Last, checkout);
+
+		app.run();
+	}
+
+	fn setup(world: &mut World) {
+		world.spawn((
+			UniqueTags::new(&["weapon", "fire"]),
+			UniqueName::new("items/fire_sword"),
+		));
+		world.spawn((UniqueTags::new(&["weapon"]), UniqueName::new("items/sword")));
+		world.spawn((UniqueTags::new(&["armor"]), UniqueName::new("items/shield")));
+		world.spawn((UniqueTags::new(&["fire"]), UniqueName::new("spells/fireball")));
+		world.spawn(UniqueName::new("itemsless"));
+
+		// Later duplicate replace tags
+		world.spawn((UniqueTags::new(&["weapon", "ice"]), UniqueName::new("items/sword")));
+	}
+
+	fn check_index(world: &mut World) {
+		let index = world.resource::<UniqueIndex>();
+		assert_eq!(index.names().count(), 5);
+		assert_eq!(
+			index.with_prefix("items/").collect::<Vec<_>>(),
+			["items/fire_sword", "items/shield", "items/sword"]
+		);
+		assert_eq!(index.tagged_all(&["weapon", "fire"]), ["items/fire_sword"]);
+		assert_eq!(index.tagged_all(&["weapon", "ice"]), ["items/sword"]);
+		assert_eq!(
+			index.tagged_any(&["fire", "armor"]),
+			["items/fire_sword", "items/shield", "spells/fireball"]
+		);
+
+		let fire_sword = world.resource::<UniqueHashed>().get_ent("items/fire_sword").unwrap();
+		world.despawn(fire_sword);
+	}
+
+	fn checkout(index: Res<UniqueIndex>) {
+		assert_eq!(index.names().count(), 4);
+		assert_eq!(index.tagged("fire").collect::<Vec<_>>(), ["spells/fireball"]);
+		assert_eq!(index.tags().collect::<Vec<_>>(), ["armor", "fire", "ice", "weapon"]);
+	}
+
+	/// Loader insert [UniqueName] after every component
+	#[test]
+	fn test_index_loaded() {
+		let mut app = App::new();
+		app.add_plugins(UniquePlugin);
+		let world = app.world_mut();
+		let registry = world.resource::<AppTypeRegistry>().clone();
+		let registry = registry.read();
+
+		let tags: Box<dyn PartialReflect> = Box::new(UniqueTags::new(&["weapon"]));
+		spawn_prototype(
+			world,
+			&registry,
+			PrototypeSource::default(),
+			UniqueName::new("items/axe"),
+			&[tags],
+		);
+		world.flush();
+
+		let index = world.resource::<UniqueIndex>();
+		assert_eq!(index.tagged("weapon").collect::<Vec<_>>(), ["items/axe"]);
+	}
+}
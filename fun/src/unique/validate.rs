@@ -0,0 +1,405 @@
+use std::fmt;
+
+use bevy_derive::*;
+use bevy_ecs::prelude::*;
+use bevy_log::prelude::*;
+use bevy_reflect::{GetPath, PartialReflect, Reflect, ReflectRef, TypeRegistry};
+use serde::{Deserialize, Serialize};
+use smol_str::{SmolStr, ToSmolStr};
+
+use super::*;
+
+/// Checked against every prototype with the tag, empty tag for every prototype.
+///
+/// Component named by type path, field by reflect path such as `.0` or `stats.health`.
+#[derive(Reflect, Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct ValidationRule {
+	pub tag: SmolStr,
+	pub required: Vec<SmolStr>,
+	pub forbidden: Vec<SmolStr>,
+	pub fields: Vec<FieldRule>,
+}
+
+#[derive(Reflect, Serialize, Deserialize, Clone, Debug)]
+pub struct FieldRule {
+	pub component: SmolStr,
+	pub path: SmolStr,
+	pub constraint: FieldConstraint,
+}
+
+#[derive(Reflect, Serialize, Deserialize, Clone, Debug)]
+pub enum FieldConstraint {
+	/// Any number primitive, inclusive.
+	Range { min: Option<f64>, max: Option<f64> },
+	/// String, list, array, map or set.
+	NonEmpty,
+	/// String value or enum variant name.
+	OneOf(Vec<SmolStr>),
+}
+
+/// Rules checked by [validate_prototypes].
+#[derive(Resource, Default, Debug, Deref, DerefMut)]
+pub struct ValidationRules(pub Vec<ValidationRule>);
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ValidationError {
+	UnknownComponent(SmolStr),
+	MissingComponent(SmolStr),
+	ForbiddenComponent(SmolStr),
+	InvalidPath {
+		component: SmolStr,
+		path: SmolStr,
+	},
+	OutOfRange {
+		component: SmolStr,
+		path: SmolStr,
+		value: f64,
+	},
+	Empty {
+		component: SmolStr,
+		path: SmolStr,
+	},
+	NotAllowed {
+		component: SmolStr,
+		path: SmolStr,
+		value: SmolStr,
+	},
+	/// Constraint can't be used on the field type.
+	Mismatch {
+		component: SmolStr,
+		path: SmolStr,
+	},
+}
+
+impl fmt::Display for ValidationError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			ValidationError::UnknownComponent(component) => write!(f, "Unregistered component {}", component),
+			ValidationError::MissingComponent(component) => write!(f, "Missing {}", component),
+			ValidationError::ForbiddenComponent(component) => write!(f, "Forbidden {}", component),
+			ValidationError::InvalidPath { component, path } => write!(f, "No field {} in {}", path, component),
+			ValidationError::OutOfRange { component, path, value } => {
+				write!(f, "{}{} out of range: {}", component, path, value)
+			}
+			ValidationError::Empty { component, path } => write!(f, "{}{} is empty", component, path),
+			ValidationError::NotAllowed { component, path, value } => {
+				write!(f, "{}{} not allowed: {}", component, path, value)
+			}
+			ValidationError::Mismatch { component, path } => {
+				write!(f, "{}{} type not match constraint", component, path)
+			}
+		}
+	}
+}
+
+/// Every error from last [validate_prototypes], by prototype name then rule order.
+#[derive(Resource, Default, Debug, Deref)]
+pub struct ValidationReport(pub Vec<(SmolStr, ValidationError)>);
+
+impl ValidationReport {
+	pub fn is_valid(&self) -> bool {
+		self.is_empty()
+	}
+
+	pub fn errors_of<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a ValidationError> {
+		self.iter()
+			.filter(move |(error_name, _)| error_name == name)
+			.map(|(_, error)| error)
+	}
+}
+
+/// Check every [ValidationRules] and replace [ValidationReport].
+pub fn validate_prototypes(world: &mut World) {
+	let registry = world.resource::<AppTypeRegistry>().clone();
+	let registry = registry.read();
+	let rules = world.resource::<ValidationRules>();
+	let index = world.resource::<UniqueIndex>();
+	let hashed = world.resource::<UniqueHashed>();
+
+	let mut errors = Vec::new();
+	for rule in rules.iter() {
+		let names = match rule.tag.is_empty() {
+			true => index.names().collect::<Vec<_>>(),
+			false => index.tagged(&rule.tag).collect(),
+		};
+		for name in names {
+			let Some(entity) = hashed.get_ent(name) else {
+				continue;
+			};
+			let ent_ref = world.entity(entity);
+			errors.extend(
+				validate_rule(rule, ent_ref, &registry)
+					.into_iter()
+					.map(|error| (name.clone(), error)),
+			);
+		}
+	}
+
+	errors.sort_by(|(name_a, _), (name_b, _)| name_a.cmp(name_b));
+	for (name, error) in &errors {
+		warn!("Prototype {} invalid: {}", name, error);
+	}
+	world.insert_resource(ValidationReport(errors));
+}
+
+fn validate_rule(rule: &ValidationRule, ent_ref: EntityRef, registry: &TypeRegistry) -> Vec<ValidationError> {
+	let reflect_component = |type_path: &SmolStr| {
+		registry
+			.get_with_type_path(type_path)
+			.and_then(|registration| registration.data::<ReflectComponent>())
+			.ok_or_else(|| ValidationError::UnknownComponent(type_path.clone()))
+	};
+
+	let mut errors = Vec::new();
+	for type_path in &rule.required {
+		match reflect_component(type_path) {
+			Ok(reflected) if reflected.contains(ent_ref) => {}
+			Ok(_) => errors.push(ValidationError::MissingComponent(type_path.clone())),
+			Err(err) => errors.push(err),
+		}
+	}
+	for type_path in &rule.forbidden {
+		match reflect_component(type_path) {
+			Ok(reflected) if reflected.contains(ent_ref) => {
+				errors.push(ValidationError::ForbiddenComponent(type_path.clone()))
+			}
+			Ok(_) => {}
+			Err(err) => errors.push(err),
+		}
+	}
+
+	for FieldRule {
+		component,
+		path,
+		constraint,
+	} in &rule.fields
+	{
+		let reflected = match reflect_component(component) {
+			Ok(reflected) => reflected,
+			Err(err) => {
+				errors.push(err);
+				continue;
+			}
+		};
+		// Missing component reported by required
+		let Some(value) = reflected.reflect(ent_ref) else {
+			continue;
+		};
+		let Ok(field) = value.reflect_path(path.as_str()) else {
+			errors.push(ValidationError::InvalidPath {
+				component: component.clone(),
+				path: path.clone(),
+			});
+			continue;
+		};
+		if let Some(error) = check_constraint(constraint, field, component, path) {
+			errors.push(error);
+		}
+	}
+	errors
+}
+
+fn check_constraint(
+	constraint: &FieldConstraint,
+	field: &dyn PartialReflect,
+	component: &SmolStr,
+	path: &SmolStr,
+) -> Option<ValidationError> {
+	let mismatch = || ValidationError::Mismatch {
+		component: component.clone(),
+		path: path.clone(),
+	};
+	match constraint {
+		FieldConstraint::Range { min, max } => {
+			let Some(value) = as_f64(field) else {
+				return Some(mismatch());
+			};
+			let below = min.is_some_and(|min| value < min);
+			let above = max.is_some_and(|max| value > max);
+			(below || above).then(|| ValidationError::OutOfRange {
+				component: component.clone(),
+				path: path.clone(),
+				value,
+			})
+		}
+		FieldConstraint::NonEmpty => {
+			let is_empty = match field.reflect_ref() {
+				ReflectRef::List(list) => list.is_empty(),
+				ReflectRef::Array(array) => array.is_empty(),
+				ReflectRef::Map(map) => map.is_empty(),
+				ReflectRef::Set(set) => set.is_empty(),
+				_ => match as_str(field) {
+					Some(value) => value.is_empty(),
+					None => return Some(mismatch()),
+				},
+			};
+			is_empty.then(|| ValidationError::Empty {
+				component: component.clone(),
+				path: path.clone(),
+			})
+		}
+		FieldConstraint::OneOf(allowed) => {
+			let value = match field.reflect_ref() {
+				ReflectRef::Enum(enumed) => enumed.variant_name().to_smolstr(),
+				_ => match as_str(field) {
+					Some(value) => value.to_smolstr(),
+					None => return Some(mismatch()),
+				},
+			};
+			(!allowed.contains(&value)).then(|| ValidationError::NotAllowed {
+				component: component.clone(),
+				path: path.clone(),
+				value,
+			})
+		}
+	}
+}
+
+fn as_f64(field: &dyn PartialReflect) -> Option<f64> {
+	macro_rules! downcast {
+		($($ty:ty),*) => {
+			$(if let Some(value) = field.try_downcast_ref::<$ty>() {
+				return Some(*value as f64);
+			})*
+		};
+	}
+	downcast!(f32, f64, i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+	None
+}
+
+fn as_str(field: &dyn PartialReflect) -> Option<&str> {
+	field
+		.try_downcast_ref::<String>()
+		.map(String::as_str)
+		.or_else(|| field.try_downcast_ref::<SmolStr>().map(SmolStr::as_str))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[derive(Component, Reflect, Default)]
+	#[reflect(Component)]
+	struct Health(u32);
+
+	#[derive(Component, Reflect, Default)]
+	#[reflect(Component)]
+	struct Speed(f32);
+
+	#[derive(Component, Reflect, Default)]
+	#[reflect(Component)]
+	struct Friendly;
+
+	#[derive(Component, Reflect, Default)]
+	#[reflect(Component)]
+	struct Info {
+		title: String,
+		faction: Faction,
+	}
+
+	#[derive(Reflect, Default)]
+	enum Faction {
+		#[default]
+		Orc,
+		Elf,
+		Human,
+	}
+
+	#[test]
+	fn test_validate() {
+		let mut app = App::new();
+		app.add_plugins(UniquePlugin)
+			.register_type::<Health>()
+			.register_type::<Speed>()
+			.register_type::<Friendly>()
+			.register_type::<Info>()
+			.add_systems(PreStartup, setup)
+			.add_systems(Startup, validate_prototypes)
+			.add_systems(Last, checkout);
+
+		app.run();
+	}
+
+	fn setup(world: &mut World) {
+		let rules = r#"[
+			(
+				tag: "enemy",
+				required: [
+					"reflection_fun::unique::validate::tests::Health",
+					"reflection_fun::unique::validate::tests::Speed",
+				],
+				forbidden: ["reflection_fun::unique::validate::tests::Friendly"],
+				fields: [
+					(
+						component: "reflection_fun::unique::validate::tests::Health",
+						path: ".0",
+						constraint: Range(min: Some(1.0), max: Some(1000.0)),
+					),
+					(
+						component: "reflection_fun::unique::validate::tests::Info",
+						path: "title",
+						constraint: NonEmpty,
+					),
+					(
+						component: "reflection_fun::unique::validate::tests::Info",
+						path: "faction",
+						constraint: OneOf(["Orc", "Human"]),
+					),
+				],
+			),
+		]"#;
+		world.insert_resource(ValidationRules(ron::from_str(rules).unwrap()));
+
+		let info = |title: &str, faction| Info {
+			title: title.to_string(),
+			faction,
+		};
+		let tags = || UniqueTags::new(&["enemy"]);
+		world.spawn((
+			tags(),
+			Health(50),
+			Speed(1.5),
+			info("Goblin", Faction::Orc),
+			UniqueName::new("Goblin"),
+		));
+		world.spawn((
+			tags(),
+			Health(0),
+			Friendly,
+			info("", Faction::Elf),
+			UniqueName::new("Broken"),
+		));
+		world.spawn((Health(0), UniqueName::new("Not_Enemy")));
+	}
+
+	fn checkout(report: Res<ValidationReport>) {
+		assert_eq!(report.errors_of("Goblin").count(), 0);
+		assert_eq!(report.errors_of("Not_Enemy").count(), 0);
+
+		let health = SmolStr::new("reflection_fun::unique::validate::tests::Health");
+		let info = SmolStr::new("reflection_fun::unique::validate::tests::Info");
+		assert_eq!(
+			report.errors_of("Broken").cloned().collect::<Vec<_>>(),
+			[
+				ValidationError::MissingComponent(SmolStr::new("reflection_fun::unique::validate::tests::Speed")),
+				ValidationError::ForbiddenComponent(SmolStr::new("reflection_fun::unique::validate::tests::Friendly")),
+				ValidationError::OutOfRange {
+					component: health,
+					path: SmolStr::new(".0"),
+					value: 0.0,
+				},
+				ValidationError::Empty {
+					component: info.clone(),
+					path: SmolStr::new("title"),
+				},
+				ValidationError::NotAllowed {
+					component: info,
+					path: SmolStr::new("faction"),
+					value: SmolStr::new("Elf"),
+				},
+			]
+		);
+		assert!(!report.is_valid());
+	}
+}
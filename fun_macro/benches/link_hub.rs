@@ -0,0 +1,80 @@
+//! Link 100k entities to one hub, one queued command per link against one merged update.
+//!
+//! ```sh
+//! cargo bench -p fun_macro --bench link_hub
+//! ```
+use std::time::{Duration, Instant};
+
+use bevy_derive::*;
+use bevy_ecs::{
+	entity::{EntityHashSet, MapEntities},
+	lifecycle::HookContext,
+	prelude::*,
+	system::RunSystemOnce,
+	world::DeferredWorld,
+};
+use fun_macro::*;
+
+relation_many! {
+	pub struct ModNotif(EntityHashSet);
+
+	pub struct ModGetNotif(EntityHashSet);
+}
+
+const LINKS: usize = 100_000;
+const RUNS: u32 = 5;
+
+fn main() {
+	bench("share_mod", |world, hub, ents| {
+		for ent in ents {
+			world
+				.commands()
+				.queue(ShareMod::<ModNotif, ModGetNotif>::new(hub, *ent));
+		}
+	});
+	bench("add_mod", |world, hub, ents| {
+		for ent in ents {
+			world.commands().entity(hub).add_mod::<ModNotif>(*ent);
+		}
+	});
+	bench("add_mods", |world, hub, ents| {
+		world.commands().entity(hub).add_mods::<ModNotif>(ents.iter().copied());
+	});
+	bench("mod_batch", |world, hub, ents| {
+		let mut batch = ModBatch::<ModNotif, ModGetNotif>::new();
+		for ent in ents {
+			batch.push(ModOp::Share, hub, *ent);
+		}
+		world.commands().queue(batch);
+	});
+	bench("deferred_batch", |world, hub, ents| {
+		world.insert_resource(Linking(hub, ents.to_vec()));
+		world.run_system_once(push_deferred).unwrap();
+	});
+}
+
+#[derive(Resource)]
+struct Linking(Entity, Vec<Entity>);
+
+fn push_deferred(linking: Res<Linking>, mut batch: Deferred<ModBatch<ModNotif, ModGetNotif>>) {
+	for ent in &linking.1 {
+		batch.push(ModOp::Share, linking.0, *ent);
+	}
+}
+
+fn bench(name: &str, link: impl Fn(&mut World, Entity, &[Entity])) {
+	let mut total = Duration::ZERO;
+	for _ in 0..RUNS {
+		let mut world = World::new();
+		let hub = world.spawn_empty().id();
+		let ents = (0..LINKS).map(|_| world.spawn_empty().id()).collect::<Vec<_>>();
+
+		let start = Instant::now();
+		link(&mut world, hub, &ents);
+		world.flush();
+		total += start.elapsed();
+
+		assert_eq!(world.get::<ModNotif>(hub).unwrap().len(), LINKS);
+	}
+	println!("{name}: {:?} per {LINKS} links", total / RUNS);
+}
@@ -0,0 +1,165 @@
+use std::{fmt, marker::PhantomData};
+
+use bevy_ecs::{entity::Entity, resource::Resource, system::Command, world::World};
+
+use crate::*;
+
+/// Link broken outside the hooks, such as a bad save or hook skipped on despawn.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LinkIssue {
+	/// Moder hold a despawned entity.
+	DeadEntity { moder: Entity, entity: Entity },
+	/// Entity hold a despawned moder.
+	DeadModer { moder: Entity, entity: Entity },
+	/// Only the moder side hold the pair.
+	MissingOnEntity { moder: Entity, entity: Entity },
+	/// Only the entity side hold the pair.
+	MissingOnModer { moder: Entity, entity: Entity },
+}
+
+impl fmt::Display for LinkIssue {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			LinkIssue::DeadEntity { moder, entity } => write!(f, "Moder {moder} hold despawned entity {entity}"),
+			LinkIssue::DeadModer { moder, entity } => write!(f, "Entity {entity} hold despawned moder {moder}"),
+			LinkIssue::MissingOnEntity { moder, entity } => {
+				write!(f, "Moder {moder} hold entity {entity}, not the other way")
+			}
+			LinkIssue::MissingOnModer { moder, entity } => {
+				write!(f, "Entity {entity} hold moder {moder}, not the other way")
+			}
+		}
+	}
+}
+
+/// Every link of `T` on the moder and `U` on the entity held by both side and alive.
+///
+/// Moder side issues first, then entity side.
+pub fn check_links<T: Many2Many, U: Many2Many>(world: &World) -> Vec<LinkIssue> {
+	let moder_side = broken_side::<T, U>(world)
+		.into_iter()
+		.map(|(moder, entity, alive)| match alive {
+			true => LinkIssue::MissingOnEntity { moder, entity },
+			false => LinkIssue::DeadEntity { moder, entity },
+		});
+	let entity_side = broken_side::<U, T>(world)
+		.into_iter()
+		.map(|(entity, moder, alive)| match alive {
+			true => LinkIssue::MissingOnModer { moder, entity },
+			false => LinkIssue::DeadModer { moder, entity },
+		});
+	moder_side.chain(entity_side).collect()
+}
+
+/// [check_links] then fix every issue, despawned entity dropped and missing side added back.
+///
+/// No event triggered and [ModConstraints] not checked, the pair was already linked on one side.
+pub fn repair_links<T: Many2Many, U: Many2Many>(world: &mut World) -> Vec<LinkIssue> {
+	let issues = check_links::<T, U>(world);
+	for issue in &issues {
+		match *issue {
+			LinkIssue::DeadEntity { moder, entity } => {
+				update_side::<T>(world, moder, &[(ModOp::Remove, entity)]);
+			}
+			LinkIssue::DeadModer { moder, entity } => {
+				update_side::<U>(world, entity, &[(ModOp::Remove, moder)]);
+			}
+			LinkIssue::MissingOnEntity { moder, entity } => {
+				update_side::<U>(world, entity, &[(ModOp::Share, moder)]);
+			}
+			LinkIssue::MissingOnModer { moder, entity } => {
+				update_side::<T>(world, moder, &[(ModOp::Share, entity)]);
+			}
+		}
+	}
+	issues
+}
+
+/// Links of `A` not held back by `B` or to a despawned entity, as `(owner, linked, alive)`.
+fn broken_side<A: Many2Many, B: Many2Many>(world: &World) -> Vec<(Entity, Entity, bool)> {
+	let Some(mut query) = world.try_query::<(Entity, &A)>() else {
+		return Vec::new();
+	};
+	query
+		.iter(world)
+		.flat_map(|(owner, notif)| {
+			notif
+				.entity_set_ref()
+				.iter_entities()
+				.filter_map(move |linked| match world.get_entity(linked) {
+					Ok(linked_ref) => (!linked_ref
+						.get::<B>()
+						.is_some_and(|other| other.entity_set_ref().contains_entity(&owner)))
+					.then_some((owner, linked, true)),
+					Err(_) => Some((owner, linked, false)),
+				})
+		})
+		.collect()
+}
+
+/// Result of the last [CheckLinks] of `T` and `U`.
+#[derive(Resource)]
+pub struct LinkReport<T: Many2Many, U: Many2Many> {
+	pub issues: Vec<LinkIssue>,
+	/// Issues already fixed by [repair_links].
+	pub repaired: bool,
+	types: PhantomData<(T, U)>,
+}
+
+impl<T: Many2Many, U: Many2Many> LinkReport<T, U> {
+	pub fn is_ok(&self) -> bool {
+		self.issues.is_empty()
+	}
+}
+
+/// [check_links] or [repair_links] as a command, [LinkReport] replaced with the result.
+pub struct CheckLinks<T: Many2Many, U: Many2Many> {
+	type_moder: PhantomData<T>,
+	type_entity: PhantomData<U>,
+	repair: bool,
+}
+
+impl<T: Many2Many, U: Many2Many> Default for CheckLinks<T, U> {
+	fn default() -> Self {
+		Self {
+			type_moder: PhantomData,
+			type_entity: PhantomData,
+			repair: false,
+		}
+	}
+}
+
+impl<T: Many2Many, U: Many2Many> CheckLinks<T, U> {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	pub fn with_repair(mut self) -> Self {
+		self.repair = true;
+		self
+	}
+
+	/// Exclusive system of the check only.
+	pub fn system(world: &mut World) {
+		Self::new().apply(world);
+	}
+
+	/// Exclusive system with repair, such as after loading a save.
+	pub fn repair_system(world: &mut World) {
+		Self::new().with_repair().apply(world);
+	}
+}
+
+impl<T: Many2Many, U: Many2Many> Command for CheckLinks<T, U> {
+	fn apply(self, world: &mut World) {
+		let issues = match self.repair {
+			true => repair_links::<T, U>(world),
+			false => check_links::<T, U>(world),
+		};
+		world.insert_resource(LinkReport::<T, U> {
+			issues,
+			repaired: self.repair,
+			types: PhantomData,
+		});
+	}
+}
@@ -0,0 +1,203 @@
+use std::fmt;
+
+use bevy_ecs::{
+	entity::{Entity, EntityHashSet},
+	world::World,
+};
+
+use crate::*;
+
+/// Limit of a [relation_many](crate::relation_many) pair, checked on every share.
+/// Not available on [relation_many_data](crate::relation_many_data).
+///
+/// ```ignore
+/// relation_many! {
+///     pub struct Parent(EntityIndexSet);
+///
+///     pub struct Child(EntityIndexSet);
+///
+///     constraints: ModConstraints::NONE.max_entity(1).no_self_link().acyclic();
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ModConstraints {
+	/// Links of one moder, the first struct side.
+	pub max_moder: Option<usize>,
+	/// Links of one entity, the second struct side.
+	pub max_entity: Option<usize>,
+	pub self_link: bool,
+	/// Moder never reach itself through the links, for transitive relation.
+	pub acyclic: bool,
+	pub policy: ViolationPolicy,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ViolationPolicy {
+	/// Share dropped with [LinkRejected].
+	#[default]
+	Reject,
+	/// Unlink links of the full side to make room, first in iteration order.
+	/// Oldest with [EntityIndexSet](bevy_ecs::entity::EntityIndexSet) storage, any link with [EntityHashSet].
+	/// Self link and cycle still rejected.
+	Evict,
+	/// Panic with debug assertions, otherwise [ViolationPolicy::Reject].
+	PanicInDebug,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ModViolation {
+	MaxModer,
+	MaxEntity,
+	SelfLink,
+	Cycle,
+}
+
+impl fmt::Display for ModViolation {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			ModViolation::MaxModer => write!(f, "Moder link limit reached"),
+			ModViolation::MaxEntity => write!(f, "Entity link limit reached"),
+			ModViolation::SelfLink => write!(f, "Self link forbidden"),
+			ModViolation::Cycle => write!(f, "Link would close a cycle"),
+		}
+	}
+}
+
+impl Default for ModConstraints {
+	fn default() -> Self {
+		Self::NONE
+	}
+}
+
+impl ModConstraints {
+	pub const NONE: Self = Self {
+		max_moder: None,
+		max_entity: None,
+		self_link: true,
+		acyclic: false,
+		policy: ViolationPolicy::Reject,
+	};
+
+	pub const fn max_moder(mut self, max: usize) -> Self {
+		self.max_moder = Some(max);
+		self
+	}
+
+	pub const fn max_entity(mut self, max: usize) -> Self {
+		self.max_entity = Some(max);
+		self
+	}
+
+	pub const fn no_self_link(mut self) -> Self {
+		self.self_link = false;
+		self
+	}
+
+	pub const fn acyclic(mut self) -> Self {
+		self.acyclic = true;
+		self
+	}
+
+	pub const fn policy(mut self, policy: ViolationPolicy) -> Self {
+		self.policy = policy;
+		self
+	}
+
+	pub fn is_none(&self) -> bool {
+		*self == Self::NONE
+	}
+
+	/// Links to remove before sharing, by [ViolationPolicy].
+	pub fn check<T: Many2Many, U: Many2Many>(
+		&self,
+		world: &World,
+		moder: Entity,
+		entity: Entity,
+	) -> Result<Vec<(Entity, Entity)>, ModViolation> {
+		let mod_notif = world.get::<T>(moder).map(|notif| notif.entity_set_ref());
+		if mod_notif.is_some_and(|set| set.contains_entity(&entity)) {
+			return Ok(Vec::new());
+		}
+		if !self.self_link && moder == entity {
+			return self.violate(ModViolation::SelfLink);
+		}
+		if self.acyclic && reaches::<T>(world, entity, moder) {
+			return self.violate(ModViolation::Cycle);
+		}
+
+		let mut evict = Vec::new();
+		if let (Some(max), Some(set)) = (self.max_moder, mod_notif)
+			&& set.len() >= max
+		{
+			if self.policy != ViolationPolicy::Evict || max == 0 {
+				return self.violate(ModViolation::MaxModer);
+			}
+			evict.extend(
+				set.iter_entities()
+					.take(set.len() + 1 - max)
+					.map(|first| (moder, first)),
+			);
+		}
+		let get_notif = world.get::<U>(entity).map(|notif| notif.entity_set_ref());
+		if let (Some(max), Some(set)) = (self.max_entity, get_notif)
+			&& set.len() >= max
+		{
+			if self.policy != ViolationPolicy::Evict || max == 0 {
+				return self.violate(ModViolation::MaxEntity);
+			}
+			evict.extend(
+				set.iter_entities()
+					.take(set.len() + 1 - max)
+					.map(|first| (first, entity)),
+			);
+		}
+		Ok(evict)
+	}
+
+	fn violate(&self, violation: ModViolation) -> Result<Vec<(Entity, Entity)>, ModViolation> {
+		if self.policy == ViolationPolicy::PanicInDebug && cfg!(debug_assertions) {
+			panic!("{}", violation);
+		}
+		Err(violation)
+	}
+}
+
+/// `to` reached from `from` through `T`, `from` itself included.
+fn reaches<T: Many2Many>(world: &World, from: Entity, to: Entity) -> bool {
+	let mut visited = EntityHashSet::default();
+	let mut stack = vec![from];
+	while let Some(current) = stack.pop() {
+		if current == to {
+			return true;
+		}
+		if visited.insert(current)
+			&& let Some(notif) = world.get::<T>(current)
+		{
+			stack.extend(notif.entity_set_ref().iter_entities());
+		}
+	}
+	false
+}
+
+/// Check [Many2Many::constraints] of `T`, evict or trigger [LinkRejected]. `false` if rejected or either side missing.
+pub(crate) fn admit<T: Many2Many, U: Many2Many>(world: &mut World, moder: Entity, entity: Entity) -> bool {
+	if world.get_entity(moder).is_err() || world.get_entity(entity).is_err() {
+		return false;
+	}
+	match T::constraints().check::<T, U>(world, moder, entity) {
+		Ok(evict) => {
+			if !evict.is_empty() {
+				let mut batch = ModBatch::<T, U>::new().with_reason(UnlinkReason::Evicted);
+				evict
+					.into_iter()
+					.for_each(|(moder, entity)| batch.push(ModOp::Remove, moder, entity));
+				batch.apply_net(world);
+			}
+			true
+		}
+		Err(violation) => {
+			trigger_rejected::<T, U>(world, moder, entity, violation);
+			false
+		}
+	}
+}
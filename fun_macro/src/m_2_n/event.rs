@@ -0,0 +1,116 @@
+use std::marker::PhantomData;
+
+use bevy_ecs::{entity::Entity, event::EntityEvent, world::World};
+
+use crate::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UnlinkReason {
+	/// [RemoveMod], bulk change or the component removed.
+	Explicit,
+	/// Either side despawned, it may not exist anymore.
+	Despawn,
+	/// Link dropped for a new one, [ViolationPolicy::Evict].
+	Evicted,
+}
+
+/// Pair linked, `T` on the moder and `U` on the entity.
+///
+/// Triggered on the moder then the entity, global observer see it once per side.
+#[derive(EntityEvent)]
+pub struct Linked<T: Many2Many, U: Many2Many> {
+	#[event_target]
+	pub target: Entity,
+	pub moder: Entity,
+	pub entity: Entity,
+	types: PhantomData<(T, U)>,
+}
+
+impl<T: Many2Many, U: Many2Many> Linked<T, U> {
+	/// Side the event is not triggered on.
+	pub fn other(&self) -> Entity {
+		if self.target == self.moder {
+			self.entity
+		} else {
+			self.moder
+		}
+	}
+}
+
+/// Pair unlinked, same as [Linked].
+#[derive(EntityEvent)]
+pub struct Unlinked<T: Many2Many, U: Many2Many> {
+	#[event_target]
+	pub target: Entity,
+	pub moder: Entity,
+	pub entity: Entity,
+	pub reason: UnlinkReason,
+	types: PhantomData<(T, U)>,
+}
+
+impl<T: Many2Many, U: Many2Many> Unlinked<T, U> {
+	/// Side the event is not triggered on.
+	pub fn other(&self) -> Entity {
+		if self.target == self.moder {
+			self.entity
+		} else {
+			self.moder
+		}
+	}
+}
+
+/// Share refused by [ModConstraints], same as [Linked], nothing changed.
+#[derive(EntityEvent)]
+pub struct LinkRejected<T: Many2Many, U: Many2Many> {
+	#[event_target]
+	pub target: Entity,
+	pub moder: Entity,
+	pub entity: Entity,
+	pub violation: ModViolation,
+	types: PhantomData<(T, U)>,
+}
+
+pub(crate) fn trigger_linked<T: Many2Many, U: Many2Many>(world: &mut World, moder: Entity, entity: Entity) {
+	for target in [moder, entity] {
+		world.trigger(Linked::<T, U> {
+			target,
+			moder,
+			entity,
+			types: PhantomData,
+		});
+	}
+}
+
+pub(crate) fn trigger_unlinked<T: Many2Many, U: Many2Many>(
+	world: &mut World,
+	moder: Entity,
+	entity: Entity,
+	reason: UnlinkReason,
+) {
+	for target in [moder, entity] {
+		world.trigger(Unlinked::<T, U> {
+			target,
+			moder,
+			entity,
+			reason,
+			types: PhantomData,
+		});
+	}
+}
+
+pub(crate) fn trigger_rejected<T: Many2Many, U: Many2Many>(
+	world: &mut World,
+	moder: Entity,
+	entity: Entity,
+	violation: ModViolation,
+) {
+	for target in [moder, entity] {
+		world.trigger(LinkRejected::<T, U> {
+			target,
+			moder,
+			entity,
+			violation,
+			types: PhantomData,
+		});
+	}
+}
@@ -0,0 +1,129 @@
+use std::iter;
+
+use bevy_derive::{Deref, DerefMut};
+use bevy_ecs::{
+	entity::{Entity, EntityHashSet},
+	world::World,
+};
+use petgraph::{
+	Direction, algo,
+	graphmap::DiGraphMap,
+	visit::{Dfs, Reversed},
+};
+
+use crate::*;
+
+/// Directed graph of links, edge from the entity holding `T` to every entity it link.
+///
+/// Several relation may be added into the same graph, such as modifier of modifier.
+#[derive(Clone, Debug, Default, Deref, DerefMut)]
+pub struct ModGraph(pub DiGraphMap<Entity, ()>);
+
+impl ModGraph {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	pub fn from_world<T: Many2Many>(world: &World) -> Self {
+		let mut graph = Self::new();
+		graph.add_relation::<T>(world);
+		graph
+	}
+
+	/// Every entity holding `T`.
+	pub fn add_relation<T: Many2Many>(&mut self, world: &World) -> &mut Self {
+		if let Some(mut query) = world.try_query::<(Entity, &T)>() {
+			self.add_links(query.iter(world));
+		}
+		self
+	}
+
+	/// From a [Query](bevy_ecs::system::Query) of `(Entity, &T)`.
+	pub fn add_links<'a, T: Many2Many>(&mut self, links: impl IntoIterator<Item = (Entity, &'a T)>) -> &mut Self {
+		for (owner, notif) in links {
+			self.0.add_node(owner);
+			for linked in notif.entity_set_ref().iter_entities() {
+				self.0.add_edge(owner, linked, ());
+			}
+		}
+		self
+	}
+
+	/// Entities reached through one link or more, itself only when on a cycle.
+	pub fn reachable(&self, entity: Entity) -> Vec<Entity> {
+		let mut dfs = Dfs::empty(&self.0);
+		dfs.stack.extend(self.0.neighbors_directed(entity, Direction::Outgoing));
+		iter::from_fn(|| dfs.next(&self.0)).collect()
+	}
+
+	/// Entities reaching `entity`, every modifier affecting it indirectly included.
+	pub fn reaching(&self, entity: Entity) -> Vec<Entity> {
+		let reversed = Reversed(&self.0);
+		let mut dfs = Dfs::empty(reversed);
+		dfs.stack.extend(self.0.neighbors_directed(entity, Direction::Incoming));
+		iter::from_fn(|| dfs.next(reversed)).collect()
+	}
+
+	pub fn is_reachable(&self, from: Entity, to: Entity) -> bool {
+		self.0.contains_node(from) && self.0.contains_node(to) && algo::has_path_connecting(&self.0, from, to, None)
+	}
+
+	/// Edge from every entity to every entity it reach.
+	pub fn transitive_closure(&self) -> Self {
+		let mut closure = DiGraphMap::new();
+		for entity in self.0.nodes() {
+			closure.add_node(entity);
+			for reached in self.reachable(entity) {
+				closure.add_edge(entity, reached, ());
+			}
+		}
+		Self(closure)
+	}
+
+	/// Fewest links from `from` to `to`, both included.
+	pub fn shortest_path(&self, from: Entity, to: Entity) -> Option<Vec<Entity>> {
+		if !self.0.contains_node(from) {
+			return None;
+		}
+		algo::astar(&self.0, from, |entity| entity == to, |_| 1u32, |_| 0).map(|(_, path)| path)
+	}
+
+	pub fn has_cycle(&self) -> bool {
+		algo::is_cyclic_directed(&self.0)
+	}
+
+	/// Entities of each cycle, every one reach every other one.
+	pub fn cycles(&self) -> Vec<Vec<Entity>> {
+		algo::tarjan_scc(&self.0)
+			.into_iter()
+			.filter(|entities| entities.len() > 1 || self.0.contains_edge(entities[0], entities[0]))
+			.collect()
+	}
+
+	/// Link direction ignored.
+	pub fn connected_components(&self) -> Vec<Vec<Entity>> {
+		let mut visited = EntityHashSet::default();
+		let mut components = Vec::new();
+		for start in self.0.nodes() {
+			if !visited.insert(start) {
+				continue;
+			}
+			let mut component = vec![start];
+			let mut index = 0;
+			while let Some(&entity) = component.get(index) {
+				index += 1;
+				let neighbors = self
+					.0
+					.neighbors_directed(entity, Direction::Outgoing)
+					.chain(self.0.neighbors_directed(entity, Direction::Incoming));
+				for neighbor in neighbors {
+					if visited.insert(neighbor) {
+						component.push(neighbor);
+					}
+				}
+			}
+			components.push(component);
+		}
+		components
+	}
+}
@@ -1,3 +1,6 @@
+/// Storage is any [ManyStorage](crate::ManyStorage), `EntityIndexSet` keep insertion order.
+/// Trigger [Linked](crate::Linked) and [Unlinked](crate::Unlinked) with the first struct as moder.
+/// Optional `constraints: expr;` last, a [ModConstraints](crate::ModConstraints) checked on every share.
 #[macro_export]
 macro_rules! relation_many {
 	(
@@ -6,28 +9,136 @@ macro_rules! relation_many {
 
         $(#[$outer_2:meta])*
 		$vis_2:vis struct $ident_2:ident($(#[$inner_2:meta])* $hashy_2:ty);
+
+		$(constraints: $constraints:expr;)?
     ) => {
         $(#[$outer_1])*
 		#[derive(Component, Default, Clone, Deref, MapEntities)]
-		#[component(immutable, on_remove = $ident_1::on_remove)]
+		#[component(immutable, on_remove = $ident_1::on_remove, on_despawn = $ident_1::on_despawn)]
 		$vis_1 struct $ident_1(#[entities] $(#[$inner_1])* $hashy_1);
 
         $(#[$outer_2])*
 		#[derive(Component, Default, Clone, Deref, MapEntities)]
-		#[component(immutable, on_remove = $ident_2::on_remove)]
+		#[component(immutable, on_remove = $ident_2::on_remove, on_despawn = $ident_2::on_despawn)]
 		$vis_2 struct $ident_2(#[entities] $(#[$inner_2])* $hashy_2);
 
-		out_entity_set!($ident_1);
-		out_entity_set!($ident_2);
+		out_entity_set!($ident_1, $hashy_1 $(, $constraints)?);
+		out_entity_set!($ident_2, $hashy_2 $(, $constraints)?);
+
+		impl $ident_1 {
+			fn on_remove(world: DeferredWorld, HookContext { entity, .. }: HookContext) {
+				unlink_moder::<$ident_1, $ident_2>(world, entity, UnlinkReason::Explicit);
+			}
+
+			fn on_despawn(world: DeferredWorld, HookContext { entity, .. }: HookContext) {
+				unlink_moder::<$ident_1, $ident_2>(world, entity, UnlinkReason::Despawn);
+			}
+		}
+
+		impl $ident_2 {
+			fn on_remove(world: DeferredWorld, HookContext { entity, .. }: HookContext) {
+				unlink_entity::<$ident_1, $ident_2>(world, entity, UnlinkReason::Explicit);
+			}
+
+			fn on_despawn(world: DeferredWorld, HookContext { entity, .. }: HookContext) {
+				unlink_entity::<$ident_1, $ident_2>(world, entity, UnlinkReason::Despawn);
+			}
+		}
+
+		impl ModSide for $ident_1 {
+			type Other = $ident_2;
+
+			fn change_now(world: &mut World, owner: Entity, change: ModsChange) {
+				ChangeMods::<$ident_1, $ident_2>::moder(owner, change).apply(world);
+			}
+		}
+
+		impl ModSide for $ident_2 {
+			type Other = $ident_1;
+
+			fn change_now(world: &mut World, owner: Entity, change: ModsChange) {
+				ChangeMods::<$ident_1, $ident_2>::entity(owner, change).apply(world);
+			}
+		}
+	};
+}
+
+#[macro_export]
+macro_rules! out_entity_set {
+	($struct_name:ident, $storage:ty) => {
+		out_entity_set!($struct_name, $storage, ModConstraints::NONE);
+	};
+	($struct_name:ident, $storage:ty, $constraints:expr) => {
+		impl Many2Many for $struct_name {
+			type Storage = $storage;
+
+			fn new_self(entity_set: $storage) -> Self {
+				Self(entity_set)
+			}
+
+			fn entity_set(self) -> $storage {
+				self.0
+			}
+
+			fn entity_set_ref(&self) -> &$storage {
+				&self.0
+			}
+
+			fn entity_set_mut(&mut self) -> &mut $storage {
+				&mut self.0
+			}
+
+			fn constraints() -> ModConstraints {
+				$constraints
+			}
+		}
+	};
+}
+
+/// [relation_many] with data on every edge, the second struct use the data type of the first.
+///
+/// ```ignore
+/// relation_many_data! {
+///     pub struct ModStrength(EntityHashMap<f32>);
+///
+///     pub struct ModGetStrength;
+/// }
+/// ```
+///
+/// Not a [Many2Many](crate::Many2Many): [ShareModData](crate::ShareModData) and
+/// [RemoveModData](crate::RemoveModData) update both side right away,
+/// no [Linked](crate::Linked) or [Unlinked](crate::Unlinked) triggered, no `constraints`,
+/// no [World](bevy_ecs::world::World) link API nor [ModBatch](crate::ModBatch).
+#[macro_export]
+macro_rules! relation_many_data {
+	(
+        $(#[$outer_1:meta])*
+		$vis_1:vis struct $ident_1:ident($(#[$inner_1:meta])* EntityHashMap<$data:ty>);
+
+        $(#[$outer_2:meta])*
+		$vis_2:vis struct $ident_2:ident;
+    ) => {
+        $(#[$outer_1])*
+		#[derive(Component, Default, Clone, Deref)]
+		#[component(immutable, map_entities, on_remove = $ident_1::on_remove)]
+		$vis_1 struct $ident_1($(#[$inner_1])* EntityHashMap<$data>);
+
+        $(#[$outer_2])*
+		#[derive(Component, Default, Clone, Deref)]
+		#[component(immutable, map_entities, on_remove = $ident_2::on_remove)]
+		$vis_2 struct $ident_2(EntityHashMap<$data>);
+
+		out_entity_map!($ident_1, $data);
+		out_entity_map!($ident_2, $data);
 
 		impl $ident_1 {
 			fn on_remove(mut world: DeferredWorld, HookContext { entity, .. }: HookContext) {
 				let ent_mut = world.entity(entity);
-				let mut mod_notif = ent_mut.get::<$ident_1>().cloned().unwrap().entity_set();
-				mod_notif.drain().for_each(|entity_notif| {
+				let mut mod_notif = ent_mut.get::<$ident_1>().cloned().unwrap().entity_map();
+				mod_notif.drain().for_each(|(entity_notif, _)| {
 					world
 						.commands()
-						.queue(RemoveMod::<$ident_1, $ident_2>::new(entity, entity_notif));
+						.queue(RemoveModData::<$ident_1, $ident_2>::new(entity, entity_notif));
 				});
 			}
 		}
@@ -35,46 +146,87 @@ macro_rules! relation_many {
 		impl $ident_2 {
 			fn on_remove(mut world: DeferredWorld, HookContext { entity, .. }: HookContext) {
 				let ent_mut = world.entity(entity);
-				let mut mod_notif = ent_mut.get::<$ident_2>().cloned().unwrap().entity_set();
-				mod_notif.drain().for_each(|entity_mod| {
+				let mut mod_notif = ent_mut.get::<$ident_2>().cloned().unwrap().entity_map();
+				mod_notif.drain().for_each(|(entity_mod, _)| {
 					world
 						.commands()
-						.queue(RemoveMod::<$ident_1, $ident_2>::new(entity_mod, entity));
+						.queue(RemoveModData::<$ident_1, $ident_2>::new(entity_mod, entity));
 				});
 			}
 		}
 
-		impl ModExtent for $ident_1 {
-			fn add_it(cmd: &mut Commands, ent_1: Entity, ent_2: Entity) {
-				cmd.queue(ShareMod::<$ident_1, $ident_2>::new(ent_2, ent_1));
+		impl ModExtentData for $ident_1 {
+			type Data = $data;
+
+			fn add_with(cmd: &mut Commands, ent_1: Entity, ent_2: Entity, data: Self::Data) {
+				cmd.queue(ShareModData::<$ident_1, $ident_2>::new(ent_2, ent_1, data));
 			}
-			fn remove_it(cmd: &mut Commands, ent_1: Entity, ent_2: Entity) {
-				cmd.queue(RemoveMod::<$ident_1, $ident_2>::new(ent_2, ent_1));
+			fn update_with(
+				cmd: &mut Commands,
+				ent_1: Entity,
+				ent_2: Entity,
+				update: impl FnOnce(&mut Self::Data) + Send + 'static,
+			) {
+				cmd.queue(UpdateModData::<$ident_1, $ident_2, _>::new(ent_2, ent_1, update));
+			}
+			fn remove_with(cmd: &mut Commands, ent_1: Entity, ent_2: Entity) {
+				cmd.queue(RemoveModData::<$ident_1, $ident_2>::new(ent_2, ent_1));
 			}
 		}
 
-		impl ModExtent for $ident_2 {
-			fn add_it(cmd: &mut Commands, ent_1: Entity, ent_2: Entity) {
-				cmd.queue(ShareMod::<$ident_1, $ident_2>::new(ent_1, ent_2));
+		impl ModExtentData for $ident_2 {
+			type Data = $data;
+
+			fn add_with(cmd: &mut Commands, ent_1: Entity, ent_2: Entity, data: Self::Data) {
+				cmd.queue(ShareModData::<$ident_1, $ident_2>::new(ent_1, ent_2, data));
 			}
-			fn remove_it(cmd: &mut Commands, ent_1: Entity, ent_2: Entity) {
-				cmd.queue(RemoveMod::<$ident_1, $ident_2>::new(ent_1, ent_2));
+			fn update_with(
+				cmd: &mut Commands,
+				ent_1: Entity,
+				ent_2: Entity,
+				update: impl FnOnce(&mut Self::Data) + Send + 'static,
+			) {
+				cmd.queue(UpdateModData::<$ident_1, $ident_2, _>::new(ent_1, ent_2, update));
+			}
+			fn remove_with(cmd: &mut Commands, ent_1: Entity, ent_2: Entity) {
+				cmd.queue(RemoveModData::<$ident_1, $ident_2>::new(ent_1, ent_2));
 			}
 		}
 	};
 }
 
 #[macro_export]
-macro_rules! out_entity_set {
-	($struct_name:ident) => {
-		impl Many2Many for $struct_name {
-			fn new_self(entity_set: EntityHashSet) -> Self {
-				Self(entity_set)
+macro_rules! out_entity_map {
+	($struct_name:ident, $data:ty) => {
+		impl Many2ManyData for $struct_name {
+			type Data = $data;
+
+			fn new_self(entity_map: EntityHashMap<$data>) -> Self {
+				Self(entity_map)
 			}
 
-			fn entity_set(self) -> EntityHashSet {
+			fn entity_map(self) -> EntityHashMap<$data> {
 				self.0
 			}
+
+			fn entity_map_ref(&self) -> &EntityHashMap<$data> {
+				&self.0
+			}
+
+			fn entity_map_mut(&mut self) -> &mut EntityHashMap<$data> {
+				&mut self.0
+			}
+		}
+
+		// Only the key are entity
+		impl MapEntities for $struct_name {
+			fn map_entities<E: EntityMapper>(&mut self, entity_mapper: &mut E) {
+				self.0 = self
+					.0
+					.drain()
+					.map(|(entity, data)| (entity_mapper.get_mapped(entity), data))
+					.collect();
+			}
 		}
 	};
 }
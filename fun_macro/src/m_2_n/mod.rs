@@ -1,5 +1,21 @@
+mod checked;
+mod constraint;
+mod event;
+mod graph;
 mod macroed;
+mod ordered;
+mod payload;
+mod query;
 mod tested;
 mod traited;
+mod world;
 
+pub use checked::*;
+pub use constraint::*;
+pub use event::*;
+pub use graph::*;
+pub use ordered::*;
+pub use payload::*;
+pub use query::*;
 pub use traited::*;
+pub use world::*;
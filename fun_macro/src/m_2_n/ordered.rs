@@ -0,0 +1,115 @@
+use std::marker::PhantomData;
+
+use bevy_ecs::{
+	entity::{Entity, EntityIndexSet},
+	system::{Command, EntityCommands},
+	world::World,
+};
+
+use crate::*;
+
+pub trait EntityCommandM2NOrdered {
+	/// Move the linked child to the index of this side order.
+	fn move_mod<T: Many2Many<Storage = EntityIndexSet>>(&mut self, child: Entity, index: usize);
+}
+
+impl<'a> EntityCommandM2NOrdered for EntityCommands<'a> {
+	fn move_mod<T: Many2Many<Storage = EntityIndexSet>>(&mut self, child: Entity, index: usize) {
+		let ent = self.id();
+		self.commands_mut().queue(MoveMod::<T>::new(ent, child, index));
+	}
+}
+
+/// [ShareMod] at the index of the moder order, move it there if already linked.
+///
+/// Index past the end append.
+pub struct ShareModAt<T: Many2Many<Storage = EntityIndexSet>, U: Many2Many> {
+	type_moder: PhantomData<T>,
+	type_entity: PhantomData<U>,
+	moder: Entity,
+	entity: Entity,
+	index: usize,
+}
+
+impl<T: Many2Many<Storage = EntityIndexSet>, U: Many2Many> ShareModAt<T, U> {
+	pub fn new(moder: Entity, entity: Entity, index: usize) -> Self {
+		Self {
+			type_moder: PhantomData,
+			type_entity: PhantomData,
+			moder,
+			entity,
+			index,
+		}
+	}
+}
+
+impl<T: Many2Many<Storage = EntityIndexSet>, U: Many2Many> Command for ShareModAt<T, U> {
+	fn apply(self, world: &mut World) {
+		if world.get_entity(self.moder).is_err() || world.get_entity(self.entity).is_err() {
+			return;
+		}
+		if !admit::<T, U>(world, self.moder, self.entity) {
+			return;
+		}
+
+		let linked = if world.get::<T>(self.moder).is_some() {
+			world
+				.modify_component::<T, _>(self.moder, |notif| {
+					let mod_notif = notif.entity_set_mut();
+					let index = self.index.min(mod_notif.len());
+					match mod_notif.get_index_of(&self.entity) {
+						Some(from) => {
+							let last = mod_notif.len() - 1;
+							mod_notif.move_index(from, index.min(last));
+							false
+						}
+						None => mod_notif.shift_insert(index, self.entity),
+					}
+				})
+				.is_ok_and(|linked| linked == Some(true))
+		} else {
+			let mod_notif = EntityIndexSet::from_iter([self.entity]);
+			world.entity_mut(self.moder).insert(T::new_self(mod_notif));
+			true
+		};
+
+		update_side::<U>(world, self.entity, &[(ModOp::Share, self.moder)]);
+		if linked {
+			trigger_linked::<T, U>(world, self.moder, self.entity);
+		}
+	}
+}
+
+/// Reorder one side only, link unchanged.
+pub struct MoveMod<T: Many2Many<Storage = EntityIndexSet>> {
+	type_side: PhantomData<T>,
+	owner: Entity,
+	linked: Entity,
+	index: usize,
+}
+
+impl<T: Many2Many<Storage = EntityIndexSet>> MoveMod<T> {
+	pub fn new(owner: Entity, linked: Entity, index: usize) -> Self {
+		Self {
+			type_side: PhantomData,
+			owner,
+			linked,
+			index,
+		}
+	}
+}
+
+impl<T: Many2Many<Storage = EntityIndexSet>> Command for MoveMod<T> {
+	fn apply(self, world: &mut World) {
+		let Some(notif) = world.get::<T>(self.owner).map(|v| v.entity_set_ref()) else {
+			return;
+		};
+		let Some(from) = notif.get_index_of(&self.linked) else {
+			return;
+		};
+		let to = self.index.min(notif.len() - 1);
+		if from != to {
+			let _ = world.modify_component::<T, _>(self.owner, |notif| notif.entity_set_mut().move_index(from, to));
+		}
+	}
+}
@@ -0,0 +1,188 @@
+use std::marker::PhantomData;
+
+use bevy_ecs::{
+	component::Component,
+	entity::{Entity, EntityHashMap},
+	system::{Command, Commands, EntityCommands},
+	world::World,
+};
+
+/// [Many2Many](crate::Many2Many) with data on every edge, both side keep the same copy.
+///
+/// Linked by the commands below only, without event nor [ModConstraints](crate::ModConstraints).
+pub trait Many2ManyData: Component + Default + Clone {
+	type Data: Clone + Send + Sync + 'static;
+
+	fn new_self(entity_map: EntityHashMap<Self::Data>) -> Self;
+	fn entity_map(self) -> EntityHashMap<Self::Data>;
+	fn entity_map_ref(&self) -> &EntityHashMap<Self::Data>;
+	/// Through [World::modify_component] only, the component is immutable.
+	fn entity_map_mut(&mut self) -> &mut EntityHashMap<Self::Data>;
+}
+
+pub trait EntityCommandM2NData {
+	fn add_mod_with<T: ModExtentData>(&mut self, child: Entity, data: T::Data);
+	fn update_mod_with<T: ModExtentData>(&mut self, child: Entity, update: impl FnOnce(&mut T::Data) + Send + 'static);
+	fn remove_mod_with<T: ModExtentData>(&mut self, child: Entity);
+}
+
+impl<'a> EntityCommandM2NData for EntityCommands<'a> {
+	fn add_mod_with<T: ModExtentData>(&mut self, child: Entity, data: T::Data) {
+		let ent = self.id();
+		T::add_with(self.commands_mut(), ent, child, data);
+	}
+	fn update_mod_with<T: ModExtentData>(&mut self, child: Entity, update: impl FnOnce(&mut T::Data) + Send + 'static) {
+		let ent = self.id();
+		T::update_with(self.commands_mut(), ent, child, update);
+	}
+	fn remove_mod_with<T: ModExtentData>(&mut self, child: Entity) {
+		let ent = self.id();
+		T::remove_with(self.commands_mut(), ent, child);
+	}
+}
+
+pub trait ModExtentData {
+	type Data: Clone + Send + Sync + 'static;
+
+	fn add_with(cmd: &mut Commands, ent_1: Entity, ent_2: Entity, data: Self::Data);
+	fn update_with(
+		cmd: &mut Commands,
+		ent_1: Entity,
+		ent_2: Entity,
+		update: impl FnOnce(&mut Self::Data) + Send + 'static,
+	);
+	fn remove_with(cmd: &mut Commands, ent_1: Entity, ent_2: Entity);
+}
+
+/// Link with data, replace the data if already linked. Nothing if either side despawned.
+pub struct ShareModData<T: Many2ManyData, U: Many2ManyData<Data = T::Data>> {
+	type_moder: PhantomData<T>,
+	type_entity: PhantomData<U>,
+	moder: Entity,
+	entity: Entity,
+	data: T::Data,
+}
+
+impl<T: Many2ManyData, U: Many2ManyData<Data = T::Data>> ShareModData<T, U> {
+	pub fn new(moder: Entity, entity: Entity, data: T::Data) -> Self {
+		Self {
+			type_moder: PhantomData,
+			type_entity: PhantomData,
+			moder,
+			entity,
+			data,
+		}
+	}
+}
+
+impl<T: Many2ManyData, U: Many2ManyData<Data = T::Data>> Command for ShareModData<T, U> {
+	fn apply(self, world: &mut World) {
+		if world.get_entity(self.moder).is_err() || world.get_entity(self.entity).is_err() {
+			return;
+		}
+		update_data_side::<T>(world, self.moder, self.entity, Some(self.data.clone()));
+		update_data_side::<U>(world, self.entity, self.moder, Some(self.data));
+	}
+}
+
+/// Change data of existing link, nothing if not linked.
+pub struct UpdateModData<T: Many2ManyData, U: Many2ManyData<Data = T::Data>, F: FnOnce(&mut T::Data) + Send + 'static> {
+	type_moder: PhantomData<T>,
+	type_entity: PhantomData<U>,
+	moder: Entity,
+	entity: Entity,
+	update: F,
+}
+
+impl<T: Many2ManyData, U: Many2ManyData<Data = T::Data>, F: FnOnce(&mut T::Data) + Send + 'static>
+	UpdateModData<T, U, F>
+{
+	pub fn new(moder: Entity, entity: Entity, update: F) -> Self {
+		Self {
+			type_moder: PhantomData,
+			type_entity: PhantomData,
+			moder,
+			entity,
+			update,
+		}
+	}
+}
+
+impl<T: Many2ManyData, U: Many2ManyData<Data = T::Data>, F: FnOnce(&mut T::Data) + Send + 'static> Command
+	for UpdateModData<T, U, F>
+{
+	fn apply(self, world: &mut World) {
+		let Some(mut data) = world
+			.get::<T>(self.moder)
+			.and_then(|notif| notif.entity_map_ref().get(&self.entity))
+			.cloned()
+		else {
+			return;
+		};
+		(self.update)(&mut data);
+		update_data_side::<T>(world, self.moder, self.entity, Some(data.clone()));
+		if world.get::<U>(self.entity).is_some() {
+			update_data_side::<U>(world, self.entity, self.moder, Some(data));
+		}
+	}
+}
+
+/// Unlink, side left empty lose its component.
+pub struct RemoveModData<T: Many2ManyData, U: Many2ManyData<Data = T::Data>> {
+	type_moder: PhantomData<T>,
+	type_entity: PhantomData<U>,
+	moder: Entity,
+	entity: Entity,
+}
+
+impl<T: Many2ManyData, U: Many2ManyData<Data = T::Data>> RemoveModData<T, U> {
+	pub fn new(moder: Entity, entity: Entity) -> Self {
+		Self {
+			type_moder: PhantomData,
+			type_entity: PhantomData,
+			moder,
+			entity,
+		}
+	}
+}
+
+impl<T: Many2ManyData, U: Many2ManyData<Data = T::Data>> Command for RemoveModData<T, U> {
+	fn apply(self, world: &mut World) {
+		update_data_side::<T>(world, self.moder, self.entity, None);
+		update_data_side::<U>(world, self.entity, self.moder, None);
+	}
+}
+
+/// Set or remove (`None`) the data of `other` on `owner` in place, component added when first linked
+/// and removed when empty. Nothing if `owner` despawned.
+fn update_data_side<T: Many2ManyData>(world: &mut World, owner: Entity, other: Entity, data: Option<T::Data>) {
+	let Ok(ent_ref) = world.get_entity(owner) else {
+		return;
+	};
+	let linked = ent_ref
+		.get::<T>()
+		.map(|notif| notif.entity_map_ref().contains_key(&other));
+	match (linked, data) {
+		(None, Some(data)) => {
+			world
+				.entity_mut(owner)
+				.insert(T::new_self(EntityHashMap::from_iter([(other, data)])));
+		}
+		(Some(_), Some(data)) => {
+			let _ = world.modify_component::<T, _>(owner, |notif| notif.entity_map_mut().insert(other, data));
+		}
+		(Some(true), None) => {
+			let is_empty = world
+				.modify_component::<T, _>(owner, |notif| {
+					let entity_map = notif.entity_map_mut();
+					entity_map.remove(&other);
+					entity_map.is_empty()
+				})
+				.is_ok_and(|is_empty| is_empty == Some(true));
+			if is_empty {
+				world.entity_mut(owner).remove::<T>();
+			}
+		}
+		(None | Some(false), None) => {}
+	}
+}
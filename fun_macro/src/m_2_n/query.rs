@@ -0,0 +1,65 @@
+use bevy_ecs::{
+	entity::Entity,
+	query::{QueryData, QueryFilter, QueryItem, ROQueryItem},
+	system::{Query, SystemParam},
+};
+
+use crate::*;
+
+/// Query `D` of the entities linked through `T`.
+///
+/// Forward start from the entity holding `T`, reverse from the entity holding the other side.
+/// `D` may not access `T` or the other side mutably, conflict panic like any [Query].
+#[derive(SystemParam)]
+pub struct LinkedQuery<'w, 's, T: ModSide, D: QueryData + 'static, F: QueryFilter + 'static = ()> {
+	links: Query<'w, 's, &'static T>,
+	reverse_links: Query<'w, 's, &'static <T as ModSide>::Other>,
+	items: Query<'w, 's, D, F>,
+}
+
+impl<'w, 's, T: ModSide, D: QueryData + 'static, F: QueryFilter + 'static> LinkedQuery<'w, 's, T, D, F> {
+	/// Entities in `entity` side `T`.
+	pub fn linked(&self, entity: Entity) -> impl Iterator<Item = Entity> + '_ {
+		self.links
+			.get(entity)
+			.into_iter()
+			.flat_map(|notif| notif.entity_set_ref().iter_entities())
+	}
+
+	/// Entities holding `T` with `entity`.
+	pub fn linked_reverse(&self, entity: Entity) -> impl Iterator<Item = Entity> + '_ {
+		self.reverse_links
+			.get(entity)
+			.into_iter()
+			.flat_map(|notif| notif.entity_set_ref().iter_entities())
+	}
+
+	/// Linked entity not matching `D` and `F` skipped.
+	pub fn iter(&self, entity: Entity) -> impl Iterator<Item = ROQueryItem<'_, 's, D>> {
+		self.items.iter_many(self.linked(entity))
+	}
+
+	pub fn iter_reverse(&self, entity: Entity) -> impl Iterator<Item = ROQueryItem<'_, 's, D>> {
+		self.items.iter_many(self.linked_reverse(entity))
+	}
+
+	pub fn for_each_mut(&mut self, entity: Entity, mut f: impl FnMut(QueryItem<'_, 's, D>)) {
+		let Ok(notif) = self.links.get(entity) else {
+			return;
+		};
+		let mut items = self.items.iter_many_mut(notif.entity_set_ref().iter_entities());
+		while let Some(item) = items.fetch_next() {
+			f(item);
+		}
+	}
+
+	pub fn for_each_reverse_mut(&mut self, entity: Entity, mut f: impl FnMut(QueryItem<'_, 's, D>)) {
+		let Ok(notif) = self.reverse_links.get(entity) else {
+			return;
+		};
+		let mut items = self.items.iter_many_mut(notif.entity_set_ref().iter_entities());
+		while let Some(item) = items.fetch_next() {
+			f(item);
+		}
+	}
+}
@@ -4,7 +4,7 @@ mod tests {
 	use bevy_app::prelude::*;
 	use bevy_derive::*;
 	use bevy_ecs::{
-		entity::{EntityHashSet, MapEntities},
+		entity::{EntityHashMap, EntityHashSet, EntityIndexSet, EntityMapper, MapEntities},
 		lifecycle::HookContext,
 		prelude::*,
 		world::DeferredWorld,
@@ -20,6 +20,18 @@ mod tests {
 		pub struct ModGetNotif(EntityHashSet);
 	}
 
+	relation_many! {
+		pub struct OrderedMod(EntityIndexSet);
+
+		pub struct OrderedGetMod(EntityIndexSet);
+	}
+
+	relation_many_data! {
+		pub struct ModStrength(EntityHashMap<f32>);
+
+		pub struct ModGetStrength;
+	}
+
 	mod total {
 		use super::*;
 
@@ -102,7 +114,7 @@ mod tests {
 		fn setup(world: &mut World) {
 			let modder = world.spawn_empty().id();
 			let get_mod = world.spawn_empty().id();
-			world.commands().entity(modder).add_mod::<ModGetNotif>(get_mod);
+			world.commands().entity(modder).add_mod::<ModNotif>(get_mod);
 		}
 
 		fn check(one_mod: Single<(Entity, &ModNotif)>, one_get_mod: Single<(Entity, &ModGetNotif)>, mut cmd: Commands) {
@@ -114,7 +126,7 @@ mod tests {
 			assert!(mod_has_get);
 			assert!(get_has_mod);
 
-			cmd.entity(one_get_mod.0).remove_mod::<ModNotif>(one_mod.0);
+			cmd.entity(one_get_mod.0).remove_mod::<ModGetNotif>(one_mod.0);
 		}
 
 		fn check_empty(one_mod: Query<&ModNotif>, one_get_mod: Query<&ModGetNotif>) {
@@ -122,4 +134,762 @@ mod tests {
 			assert!(one_get_mod.is_empty());
 		}
 	}
+
+	mod payload {
+		use super::*;
+
+		#[derive(Resource)]
+		struct Linked {
+			modder: Entity,
+			ent_1: Entity,
+			ent_2: Entity,
+		}
+
+		#[test]
+		fn test_payload() {
+			let mut app = App::new();
+			app.add_systems(PreStartup, setup);
+			app.add_systems(Startup, check_update);
+			app.add_systems(PostStartup, check_updated);
+			app.add_systems(Last, check_despawned);
+			app.run();
+		}
+
+		fn setup(world: &mut World) {
+			let modder = world.spawn_empty().id();
+			let ent_1 = world.spawn_empty().id();
+			let ent_2 = world.spawn_empty().id();
+			world
+				.commands()
+				.entity(modder)
+				.add_mod_with::<ModGetStrength>(ent_1, 1.5);
+			world
+				.commands()
+				.entity(modder)
+				.add_mod_with::<ModGetStrength>(ent_2, 2.0);
+			// Replace
+			world.commands().entity(ent_2).add_mod_with::<ModStrength>(modder, 4.0);
+			world.insert_resource(Linked { modder, ent_1, ent_2 });
+		}
+
+		fn check_update(
+			linked: Res<Linked>,
+			q_mod: Query<&ModStrength>,
+			q_get: Query<&ModGetStrength>,
+			mut cmd: Commands,
+		) {
+			let strength = q_mod.get(linked.modder).unwrap();
+			assert_eq!(strength.len(), 2);
+			assert_eq!(strength[&linked.ent_1], 1.5);
+			assert_eq!(strength[&linked.ent_2], 4.0);
+			assert_eq!(q_get.get(linked.ent_2).unwrap()[&linked.modder], 4.0);
+
+			cmd.entity(linked.modder)
+				.update_mod_with::<ModGetStrength>(linked.ent_1, |strength| *strength *= 2.0);
+			cmd.entity(linked.modder)
+				.remove_mod_with::<ModGetStrength>(linked.ent_2);
+		}
+
+		fn check_updated(
+			linked: Res<Linked>,
+			q_mod: Query<&ModStrength>,
+			q_get: Query<&ModGetStrength>,
+			mut cmd: Commands,
+		) {
+			let strength = q_mod.get(linked.modder).unwrap();
+			assert_eq!(strength.len(), 1);
+			assert_eq!(strength[&linked.ent_1], 3.0);
+			assert_eq!(q_get.get(linked.ent_1).unwrap()[&linked.modder], 3.0);
+			assert!(q_get.get(linked.ent_2).is_err());
+
+			cmd.entity(linked.modder).despawn();
+		}
+
+		fn check_despawned(q_mod: Query<&ModStrength>, q_get: Query<&ModGetStrength>) {
+			assert!(q_mod.is_empty());
+			assert!(q_get.is_empty());
+		}
+
+		/// Despawned side skipped, the rest applied without a flush
+		#[test]
+		fn test_payload_despawned() {
+			let mut world = World::new();
+			let modder = world.spawn_empty().id();
+			let ent = world.spawn_empty().id();
+			let gone = world.spawn_empty().id();
+			world.despawn(gone);
+
+			ShareModData::<ModStrength, ModGetStrength>::new(modder, ent, 1.0).apply(&mut world);
+			ShareModData::<ModStrength, ModGetStrength>::new(modder, gone, 2.0).apply(&mut world);
+			UpdateModData::<ModStrength, ModGetStrength, _>::new(gone, ent, |strength| *strength = 3.0)
+				.apply(&mut world);
+			assert_eq!(world.get::<ModStrength>(modder).unwrap().len(), 1);
+			assert_eq!(world.get::<ModGetStrength>(ent).unwrap()[&modder], 1.0);
+
+			UpdateModData::<ModStrength, ModGetStrength, _>::new(modder, ent, |strength| *strength = 3.0)
+				.apply(&mut world);
+			assert_eq!(world.get::<ModGetStrength>(ent).unwrap()[&modder], 3.0);
+
+			RemoveModData::<ModStrength, ModGetStrength>::new(modder, ent).apply(&mut world);
+			assert!(world.get::<ModStrength>(modder).is_none());
+			assert!(world.get::<ModGetStrength>(ent).is_none());
+		}
+	}
+
+	mod ordered {
+		use super::*;
+
+		#[derive(Resource)]
+		struct Spawned(Vec<Entity>);
+
+		#[test]
+		fn test_ordered() {
+			let mut app = App::new();
+			app.add_systems(PreStartup, setup);
+			app.add_systems(Startup, reorder);
+			app.add_systems(PostStartup, check_reordered);
+			app.run();
+		}
+
+		fn setup(world: &mut World) {
+			let modder = world.spawn_empty().id();
+			let ents = (0..4).map(|_| world.spawn_empty().id()).collect::<Vec<_>>();
+			for ent in ents.iter().rev() {
+				world.commands().entity(modder).add_mod::<OrderedMod>(*ent);
+			}
+			world.insert_resource(Spawned([vec![modder], ents].concat()));
+		}
+
+		fn reorder(spawned: Res<Spawned>, q_mod: Query<&OrderedMod>, mut cmd: Commands) {
+			let [modder, ent_0, ent_1, ent_2, ent_3] = spawned.0[..] else {
+				unreachable!();
+			};
+			let order = q_mod.get(modder).unwrap().iter().cloned().collect::<Vec<_>>();
+			assert_eq!(order, [ent_3, ent_2, ent_1, ent_0]);
+
+			let ent_4 = cmd.spawn_empty().id();
+			cmd.queue(ShareModAt::<OrderedMod, OrderedGetMod>::new(modder, ent_4, 1));
+			cmd.entity(modder).move_mod::<OrderedMod>(ent_0, 0);
+			cmd.entity(modder).remove_mod::<OrderedMod>(ent_2);
+		}
+
+		fn check_reordered(spawned: Res<Spawned>, q_mod: Query<&OrderedMod>, q_get: Query<(Entity, &OrderedGetMod)>) {
+			let [modder, ent_0, ent_1, _, ent_3] = spawned.0[..] else {
+				unreachable!();
+			};
+			let order = q_mod.get(modder).unwrap().iter().cloned().collect::<Vec<_>>();
+			assert_eq!(order[0], ent_0);
+			assert_eq!(order[1], ent_3);
+			assert_eq!(order[3], ent_1);
+			assert_eq!(order.len(), 4);
+
+			// Every linked entity still link back
+			assert_eq!(q_get.iter().len(), 4);
+			assert!(
+				q_get
+					.iter()
+					.all(|(ent, get_mod)| get_mod.contains(&modder) && order.contains(&ent))
+			);
+		}
+
+		/// Despawned on either side, nothing linked nor respawned
+		#[test]
+		fn test_share_at_despawned() {
+			let mut world = World::new();
+			let modder = world.spawn_empty().id();
+			let ent = world.spawn_empty().id();
+			let gone = world.spawn_empty().id();
+			world.despawn(gone);
+
+			ShareModAt::<OrderedMod, OrderedGetMod>::new(modder, ent, 0).apply(&mut world);
+			ShareModAt::<OrderedMod, OrderedGetMod>::new(modder, gone, 0).apply(&mut world);
+			ShareModAt::<OrderedMod, OrderedGetMod>::new(gone, ent, 0).apply(&mut world);
+
+			assert!(world.linked::<OrderedMod>(modder).eq([ent]));
+			assert!(world.linked::<OrderedGetMod>(ent).eq([modder]));
+			assert!(world.get_entity(gone).is_err());
+		}
+	}
+
+	mod batch {
+		use bevy_ecs::system::RunSystemOnce;
+
+		use super::*;
+
+		#[derive(Resource)]
+		struct Spawned(Vec<Entity>);
+
+		/// [ModNotif] insert, all on the hub
+		#[derive(Resource, Default)]
+		struct Inserted(usize);
+
+		#[test]
+		fn test_batch() {
+			let mut app = App::new();
+			app.init_resource::<Inserted>();
+			app.add_observer(|_: On<Insert, ModNotif>, mut inserted: ResMut<Inserted>| inserted.0 += 1);
+			app.add_systems(PreStartup, setup);
+			app.add_systems(Startup, relink);
+			app.add_systems(PostStartup, check_relinked);
+			app.add_systems(Last, check_unlinked);
+			app.run();
+		}
+
+		fn setup(world: &mut World) {
+			let hub = world.spawn_empty().id();
+			let ents = (0..100).map(|_| world.spawn_empty().id()).collect::<Vec<_>>();
+			let mut batch = ModBatch::<ModNotif, ModGetNotif>::new();
+			for ent in &ents {
+				batch.push(ModOp::Share, hub, *ent);
+			}
+			// Duplicate and share then remove, nothing more
+			let batch = batch.share(hub, ents[0]).remove(hub, ents[99]);
+			world.commands().queue(batch);
+			world.insert_resource(Spawned([vec![hub], ents].concat()));
+		}
+
+		fn relink(
+			spawned: Res<Spawned>,
+			inserted: Res<Inserted>,
+			q_mod: Query<&ModNotif>,
+			q_get: Query<&ModGetNotif>,
+			mut cmd: Commands,
+		) {
+			let hub = spawned.0[0];
+			assert_eq!(inserted.0, 1);
+			assert_eq!(q_mod.get(hub).unwrap().len(), 99);
+			assert_eq!(q_get.iter().len(), 99);
+			assert!(q_get.get(spawned.0[100]).is_err());
+
+			// Already linked, not touched
+			cmd.queue(ShareMod::<ModNotif, ModGetNotif>::new(hub, spawned.0[1]));
+			cmd.queue(ShareMod::<ModNotif, ModGetNotif>::new(hub, spawned.0[100]));
+		}
+
+		fn check_relinked(spawned: Res<Spawned>, inserted: Res<Inserted>, q_mod: Query<&ModNotif>, mut cmd: Commands) {
+			let hub = spawned.0[0];
+			assert_eq!(inserted.0, 2);
+			assert_eq!(q_mod.get(hub).unwrap().len(), 100);
+
+			let mut batch = ModBatch::<ModNotif, ModGetNotif>::new();
+			for ent in &spawned.0[1..] {
+				batch.push(ModOp::Remove, hub, *ent);
+			}
+			cmd.queue(batch);
+		}
+
+		fn check_unlinked(inserted: Res<Inserted>, q_mod: Query<&ModNotif>, q_get: Query<&ModGetNotif>) {
+			assert_eq!(inserted.0, 2);
+			assert!(q_mod.is_empty());
+			assert!(q_get.is_empty());
+		}
+
+		/// Every op of the system in one update
+		#[test]
+		fn test_deferred_batch() {
+			let mut world = World::new();
+			world.init_resource::<Inserted>();
+			world.add_observer(|_: On<Insert, ModNotif>, mut inserted: ResMut<Inserted>| inserted.0 += 1);
+			let hub = world.spawn_empty().id();
+			let ents = (0..10).map(|_| world.spawn_empty().id()).collect::<Vec<_>>();
+			world.insert_resource(Spawned([vec![hub], ents].concat()));
+
+			world.run_system_once(push_deferred).unwrap();
+			assert_eq!(world.resource::<Inserted>().0, 1);
+			assert_eq!(world.linked::<ModNotif>(hub).count(), 9);
+			assert_eq!(world.query::<&ModGetNotif>().iter(&world).len(), 9);
+		}
+
+		fn push_deferred(spawned: Res<Spawned>, mut batch: Deferred<ModBatch<ModNotif, ModGetNotif>>) {
+			let hub = spawned.0[0];
+			for ent in &spawned.0[1..] {
+				batch.push(ModOp::Share, hub, *ent);
+			}
+			batch.push(ModOp::Remove, hub, spawned.0[1]);
+		}
+	}
+
+	mod bulk {
+		use super::*;
+
+		#[derive(Resource)]
+		struct Spawned(Vec<Entity>);
+
+		#[test]
+		fn test_bulk() {
+			let mut app = App::new();
+			app.add_systems(PreStartup, setup);
+			app.add_systems(Startup, replace);
+			app.add_systems(PostStartup, clear);
+			app.add_systems(Last, check_cleared);
+			app.run();
+		}
+
+		fn setup(world: &mut World) {
+			let hub = world.spawn_empty().id();
+			let ents = (0..6).map(|_| world.spawn_empty().id()).collect::<Vec<_>>();
+			world
+				.commands()
+				.entity(hub)
+				.add_mods::<ModNotif>(ents[0..4].iter().copied());
+			// Other side
+			world.commands().entity(ents[5]).add_mods::<ModGetNotif>([hub]);
+			world.insert_resource(Spawned([vec![hub], ents].concat()));
+		}
+
+		fn replace(spawned: Res<Spawned>, q_mod: Query<&ModNotif>, q_get: Query<&ModGetNotif>, mut cmd: Commands) {
+			let [hub, ent_0, _, _, ent_3, ent_4, ent_5] = spawned.0[..] else {
+				unreachable!();
+			};
+			assert_eq!(q_mod.get(hub).unwrap().len(), 5);
+			assert_eq!(q_get.iter().len(), 5);
+			assert!(q_get.get(ent_0).unwrap().contains(&hub));
+
+			cmd.entity(hub).replace_mods::<ModNotif>([ent_3, ent_4, ent_5]);
+			cmd.entity(hub).remove_mods::<ModNotif>([ent_5]);
+		}
+
+		fn clear(spawned: Res<Spawned>, q_mod: Query<&ModNotif>, q_get: Query<&ModGetNotif>, mut cmd: Commands) {
+			let [hub, ent_0, _, _, ent_3, ent_4, _] = spawned.0[..] else {
+				unreachable!();
+			};
+			let linked = q_mod.get(hub).unwrap();
+			assert_eq!(linked.len(), 2);
+			assert!(linked.contains(&ent_3) && linked.contains(&ent_4));
+			assert_eq!(q_get.iter().len(), 2);
+			assert!(q_get.get(ent_0).is_err());
+
+			cmd.entity(ent_3).clear_mods::<ModGetNotif>();
+		}
+
+		fn check_cleared(spawned: Res<Spawned>, q_mod: Query<&ModNotif>, q_get: Query<(Entity, &ModGetNotif)>) {
+			let [hub, _, _, _, _, ent_4, _] = spawned.0[..] else {
+				unreachable!();
+			};
+			assert_eq!(q_mod.get(hub).unwrap().len(), 1);
+			assert_eq!(q_get.single().unwrap().0, ent_4);
+		}
+	}
+
+	mod events {
+		use super::*;
+
+		#[derive(Resource)]
+		struct Spawned([Entity; 3]);
+
+		/// Linked as `None`, unlinked with the reason
+		#[derive(Resource, Default)]
+		struct Received(Vec<(Entity, Entity, Option<UnlinkReason>)>);
+
+		/// Observed on ent_1 only
+		#[derive(Resource, Default)]
+		struct OnEnt1(usize);
+
+		#[test]
+		fn test_events() {
+			let mut app = App::new();
+			app.init_resource::<Received>();
+			app.init_resource::<OnEnt1>();
+			app.add_observer(
+				|event: On<Linked<ModNotif, ModGetNotif>>, mut received: ResMut<Received>| {
+					received.0.push((event.target, event.other(), None));
+				},
+			);
+			app.add_observer(
+				|event: On<Unlinked<ModNotif, ModGetNotif>>, mut received: ResMut<Received>| {
+					received.0.push((event.target, event.other(), Some(event.reason)));
+				},
+			);
+			app.add_systems(PreStartup, setup);
+			app.add_systems(Startup, unlink);
+			app.add_systems(Last, checkout);
+			app.run();
+		}
+
+		fn setup(world: &mut World) {
+			let modder = world.spawn_empty().id();
+			let ent_1 = world
+				.spawn_empty()
+				.observe(|_: On<Linked<ModNotif, ModGetNotif>>, mut on_ent_1: ResMut<OnEnt1>| on_ent_1.0 += 1)
+				.observe(|_: On<Unlinked<ModNotif, ModGetNotif>>, mut on_ent_1: ResMut<OnEnt1>| on_ent_1.0 += 1)
+				.id();
+			let ent_2 = world.spawn_empty().id();
+			world
+				.commands()
+				.queue(ShareMod::<ModNotif, ModGetNotif>::new(modder, ent_1));
+			world
+				.commands()
+				.queue(ShareMod::<ModNotif, ModGetNotif>::new(modder, ent_2));
+			// Already linked, nothing triggered
+			world
+				.commands()
+				.queue(ShareMod::<ModNotif, ModGetNotif>::new(modder, ent_1));
+			world.insert_resource(Spawned([modder, ent_1, ent_2]));
+		}
+
+		fn unlink(spawned: Res<Spawned>, mut cmd: Commands) {
+			let [modder, ent_1, ent_2] = spawned.0;
+			cmd.entity(modder).remove_mod::<ModNotif>(ent_1);
+			cmd.entity(ent_2).despawn();
+		}
+
+		fn checkout(spawned: Res<Spawned>, received: Res<Received>, on_ent_1: Res<OnEnt1>) {
+			let [modder, ent_1, ent_2] = spawned.0;
+			let explicit = Some(UnlinkReason::Explicit);
+			let despawn = Some(UnlinkReason::Despawn);
+			assert_eq!(
+				received.0,
+				[
+					(modder, ent_1, None),
+					(ent_1, modder, None),
+					(modder, ent_2, None),
+					(ent_2, modder, None),
+					(modder, ent_1, explicit),
+					(ent_1, modder, explicit),
+					(modder, ent_2, despawn),
+					(ent_2, modder, despawn),
+				]
+			);
+			assert_eq!(on_ent_1.0, 2);
+		}
+	}
+
+	mod world_api {
+		use super::*;
+
+		/// Every change visible right away, no schedule needed
+		#[test]
+		fn test_world_api() {
+			let mut world = World::new();
+			let modder = world.spawn_empty().id();
+			let ent_1 = world.spawn_empty().id();
+			let ent_2 = world.spawn_empty().id();
+
+			world.link::<ModNotif>(modder, ent_1);
+			world
+				.entity_mut(ent_2)
+				.link::<ModGetNotif>(modder)
+				.link::<ModGetNotif>(modder);
+			let linked = world.linked::<ModNotif>(modder).collect::<EntityHashSet>();
+			assert_eq!(linked, EntityHashSet::from_iter([ent_1, ent_2]));
+			assert!(world.linked::<ModGetNotif>(ent_1).eq([modder]));
+			assert!(world.entity(ent_2).get::<ModGetNotif>().unwrap().contains(&modder));
+
+			world.entity_mut(modder).unlink::<ModNotif>(ent_1);
+			assert!(world.linked::<ModNotif>(modder).eq([ent_2]));
+			assert!(world.get::<ModGetNotif>(ent_1).is_none());
+
+			world.despawn(ent_2);
+			assert!(world.get::<ModNotif>(modder).is_none());
+			assert_eq!(world.linked::<ModNotif>(ent_2).count(), 0);
+		}
+
+		/// Commands mean the same side as the World API
+		#[test]
+		fn test_same_as_commands() {
+			let mut world = World::new();
+			let [linker, linked, adder, added] = [(); 4].map(|_| world.spawn_empty().id());
+
+			world.entity_mut(linker).link::<ModGetNotif>(linked);
+			world.commands().entity(adder).add_mod::<ModGetNotif>(added);
+			world.flush();
+			assert!(world.linked::<ModGetNotif>(linker).eq([linked]));
+			assert!(world.linked::<ModGetNotif>(adder).eq([added]));
+			assert!(world.linked::<ModNotif>(linked).eq([linker]));
+			assert!(world.linked::<ModNotif>(added).eq([adder]));
+
+			world.entity_mut(linker).unlink::<ModGetNotif>(linked);
+			world.commands().entity(adder).remove_mod::<ModGetNotif>(added);
+			world.flush();
+			assert!(world.get::<ModNotif>(linked).is_none());
+			assert!(world.get::<ModNotif>(added).is_none());
+		}
+	}
+
+	mod linked_query {
+		use bevy_ecs::system::RunSystemOnce;
+
+		use super::*;
+
+		#[derive(Component)]
+		struct Power(u32);
+
+		#[derive(Component)]
+		struct Health(u32);
+
+		#[derive(Resource)]
+		struct Spawned([Entity; 5]);
+
+		#[test]
+		fn test_linked_query() {
+			let mut world = World::new();
+			let mod_1 = world.spawn(Power(2)).id();
+			let mod_2 = world.spawn(Power(3)).id();
+			let mod_3 = world.spawn_empty().id();
+			let ent_1 = world.spawn(Health(10)).id();
+			let ent_2 = world.spawn(Health(20)).id();
+			world.link::<ModNotif>(mod_1, ent_1);
+			world.link::<ModNotif>(mod_1, ent_2);
+			world.link::<ModNotif>(mod_2, ent_1);
+			world.link::<ModNotif>(mod_3, ent_1);
+			world.insert_resource(Spawned([mod_1, mod_2, mod_3, ent_1, ent_2]));
+
+			world.run_system_once(total_power).unwrap();
+			world.run_system_once(heal).unwrap();
+			assert_eq!(world.get::<Health>(ent_1).unwrap().0, 11);
+			assert_eq!(world.get::<Health>(ent_2).unwrap().0, 21);
+		}
+
+		fn total_power(spawned: Res<Spawned>, q_power: LinkedQuery<ModNotif, &Power>) {
+			let [mod_1, _, mod_3, ent_1, ent_2] = spawned.0;
+			// mod_3 has no power
+			assert_eq!(q_power.linked_reverse(ent_1).count(), 3);
+			assert_eq!(q_power.iter_reverse(ent_1).map(|power| power.0).sum::<u32>(), 5);
+			assert_eq!(q_power.iter_reverse(ent_2).map(|power| power.0).sum::<u32>(), 2);
+			assert_eq!(q_power.linked(mod_1).count(), 2);
+			assert_eq!(q_power.iter(mod_3).count(), 0);
+		}
+
+		fn heal(spawned: Res<Spawned>, mut q_health: LinkedQuery<ModNotif, &mut Health>) {
+			let [mod_1, ..] = spawned.0;
+			q_health.for_each_mut(mod_1, |mut health| health.0 += 1);
+		}
+
+		#[test]
+		#[should_panic(expected = "conflicts")]
+		fn test_linked_query_conflict() {
+			let mut world = World::new();
+			let _ = world.run_system_once(|_: LinkedQuery<ModNotif, &mut Health>, _: Query<&Health>| {});
+		}
+	}
+
+	mod graph {
+		use super::*;
+
+		#[test]
+		fn test_graph() {
+			let mut world = World::new();
+			let [mod_mod, modder, ent_1, ent_2, ent_3, alone_1, alone_2] = [(); 7].map(|_| world.spawn_empty().id());
+			world.link::<ModNotif>(mod_mod, modder);
+			world.link::<ModNotif>(modder, ent_1);
+			world.link::<ModNotif>(modder, ent_2);
+			world.link::<ModNotif>(alone_1, alone_2);
+			// Other relation in the same graph
+			world.link::<OrderedMod>(ent_1, ent_3);
+
+			let mut graph = ModGraph::from_world::<ModNotif>(&world);
+			graph.add_relation::<OrderedMod>(&world);
+			assert_eq!(graph.edge_count(), 5);
+
+			let reaching = graph.reaching(ent_3).into_iter().collect::<EntityHashSet>();
+			assert_eq!(reaching, EntityHashSet::from_iter([mod_mod, modder, ent_1]));
+			assert_eq!(graph.reachable(mod_mod).len(), 4);
+			assert!(graph.is_reachable(mod_mod, ent_3));
+			assert!(!graph.is_reachable(ent_3, mod_mod));
+			assert_eq!(
+				graph.shortest_path(mod_mod, ent_3),
+				Some(vec![mod_mod, modder, ent_1, ent_3])
+			);
+			assert_eq!(graph.shortest_path(ent_2, ent_3), None);
+
+			let closure = graph.transitive_closure();
+			assert_eq!(closure.neighbors(mod_mod).count(), 4);
+			assert!(closure.contains_edge(modder, ent_3));
+
+			let mut components = graph.connected_components();
+			components.sort_by_key(|component| component.len());
+			assert_eq!(components.len(), 2);
+			assert_eq!(components[0].len(), 2);
+			assert_eq!(components[1].len(), 5);
+
+			assert!(!graph.has_cycle());
+			assert!(graph.cycles().is_empty());
+			// Closed only through both relation
+			world.link::<ModNotif>(ent_3, mod_mod);
+			assert!(!ModGraph::from_world::<ModNotif>(&world).has_cycle());
+			let mut graph = ModGraph::from_world::<ModNotif>(&world);
+			graph.add_relation::<OrderedMod>(&world);
+			assert!(graph.has_cycle());
+			let cycles = graph.cycles();
+			assert_eq!(cycles.len(), 1);
+			assert_eq!(
+				cycles[0].iter().copied().collect::<EntityHashSet>(),
+				EntityHashSet::from_iter([mod_mod, modder, ent_1, ent_3])
+			);
+			assert!(graph.reachable(modder).contains(&modder));
+		}
+	}
+
+	mod constraint {
+		use super::*;
+
+		relation_many! {
+			pub struct LimitMod(EntityIndexSet);
+
+			pub struct LimitGetMod(EntityIndexSet);
+
+			constraints: ModConstraints::NONE.max_moder(2).no_self_link().acyclic();
+		}
+
+		relation_many! {
+			pub struct EvictMod(EntityIndexSet);
+
+			pub struct EvictGetMod(EntityIndexSet);
+
+			constraints: ModConstraints::NONE.max_entity(1).policy(ViolationPolicy::Evict);
+		}
+
+		relation_many! {
+			pub struct StrictMod(EntityHashSet);
+
+			pub struct StrictGetMod(EntityHashSet);
+
+			constraints: ModConstraints::NONE.no_self_link().policy(ViolationPolicy::PanicInDebug);
+		}
+
+		/// Observed on the moder only
+		#[derive(Resource, Default)]
+		struct Rejected(Vec<ModViolation>);
+
+		#[derive(Resource, Default)]
+		struct Evicted(Vec<(Entity, Entity)>);
+
+		#[test]
+		fn test_reject() {
+			let mut world = World::new();
+			world.init_resource::<Rejected>();
+			world.add_observer(
+				|event: On<LinkRejected<LimitMod, LimitGetMod>>, mut rejected: ResMut<Rejected>| {
+					if event.target == event.moder {
+						rejected.0.push(event.violation);
+					}
+				},
+			);
+			let [modder, ent_1, ent_2, ent_3] = [(); 4].map(|_| world.spawn_empty().id());
+
+			world.link::<LimitMod>(modder, ent_1);
+			world.link::<LimitGetMod>(ent_2, modder);
+			world.link::<LimitMod>(modder, ent_3);
+			world.link::<LimitMod>(modder, modder);
+			// Already linked, no violation
+			world.link::<LimitMod>(modder, ent_1);
+			assert!(world.linked::<LimitMod>(modder).eq([ent_1, ent_2]));
+			assert!(world.get::<LimitGetMod>(ent_3).is_none());
+
+			// modder -> ent_1 -> ent_3 -> modder
+			world.link::<LimitMod>(ent_1, modder);
+			world.link::<LimitMod>(ent_1, ent_3);
+			world.link::<LimitMod>(ent_3, modder);
+			assert!(world.linked::<LimitMod>(ent_1).eq([ent_3]));
+			assert!(world.get::<LimitMod>(ent_3).is_none());
+
+			// Checked op by op, second share see the first
+			world.unlink::<LimitMod>(modder, ent_2);
+			world.commands().queue(
+				ModBatch::<LimitMod, LimitGetMod>::new()
+					.share(modder, ent_3)
+					.share(modder, ent_2),
+			);
+			world.flush();
+			assert!(world.linked::<LimitMod>(modder).eq([ent_1, ent_3]));
+
+			// Self link triggered on both side, the same entity
+			use ModViolation::*;
+			assert_eq!(
+				world.resource::<Rejected>().0,
+				[MaxModer, SelfLink, SelfLink, Cycle, Cycle, MaxModer]
+			);
+		}
+
+		#[test]
+		fn test_evict() {
+			let mut world = World::new();
+			world.init_resource::<Evicted>();
+			world.add_observer(
+				|event: On<Unlinked<EvictMod, EvictGetMod>>, mut evicted: ResMut<Evicted>| {
+					if event.target == event.moder && event.reason == UnlinkReason::Evicted {
+						evicted.0.push((event.moder, event.entity));
+					}
+				},
+			);
+			let [mod_1, mod_2, ent] = [(); 3].map(|_| world.spawn_empty().id());
+
+			world.link::<EvictMod>(mod_1, ent);
+			world.link::<EvictMod>(mod_2, ent);
+			assert!(world.linked::<EvictGetMod>(ent).eq([mod_2]));
+			assert!(world.get::<EvictMod>(mod_1).is_none());
+			assert_eq!(world.resource::<Evicted>().0, [(mod_1, ent)]);
+		}
+
+		#[test]
+		#[should_panic(expected = "Self link forbidden")]
+		fn test_panic_in_debug() {
+			let mut world = World::new();
+			let modder = world.spawn_empty().id();
+			world.link::<StrictMod>(modder, modder);
+		}
+	}
+
+	mod checked {
+		use bevy_platform::collections::HashSet;
+
+		use super::*;
+
+		/// Links written without the hooks, then repaired
+		#[test]
+		fn test_check_repair() {
+			let mut world = World::new();
+			let dead = world.spawn_empty().id();
+			world.despawn(dead);
+			let [ent_1, ent_2, ent_3] = [(); 3].map(|_| world.spawn_empty().id());
+			let modder = world
+				.spawn(ModNotif::new_self(EntityHashSet::from_iter([ent_1, ent_2, dead])))
+				.id();
+			world
+				.entity_mut(ent_1)
+				.insert(ModGetNotif::new_self(EntityHashSet::from_iter([modder])));
+			world
+				.entity_mut(ent_3)
+				.insert(ModGetNotif::new_self(EntityHashSet::from_iter([modder, dead])));
+
+			let issues = check_links::<ModNotif, ModGetNotif>(&world);
+			let expected = [
+				LinkIssue::DeadEntity {
+					moder: modder,
+					entity: dead,
+				},
+				LinkIssue::MissingOnEntity {
+					moder: modder,
+					entity: ent_2,
+				},
+				LinkIssue::DeadModer {
+					moder: dead,
+					entity: ent_3,
+				},
+				LinkIssue::MissingOnModer {
+					moder: modder,
+					entity: ent_3,
+				},
+			];
+			assert_eq!(issues.into_iter().collect::<HashSet<_>>(), HashSet::from_iter(expected));
+
+			world
+				.commands()
+				.queue(CheckLinks::<ModNotif, ModGetNotif>::new().with_repair());
+			world.flush();
+			let report = world.resource::<LinkReport<ModNotif, ModGetNotif>>();
+			assert!(report.repaired);
+			assert_eq!(report.issues.len(), 4);
+			assert!(check_links::<ModNotif, ModGetNotif>(&world).is_empty());
+			let linked = world.linked::<ModNotif>(modder).collect::<EntityHashSet>();
+			assert_eq!(linked, EntityHashSet::from_iter([ent_1, ent_2, ent_3]));
+
+			// Hooks work again once repaired
+			world.despawn(modder);
+			assert!(world.get::<ModGetNotif>(ent_2).is_none());
+			assert!(world.get::<ModGetNotif>(ent_3).is_none());
+			CheckLinks::<ModNotif, ModGetNotif>::system(&mut world);
+			assert!(world.resource::<LinkReport<ModNotif, ModGetNotif>>().is_ok());
+		}
+	}
 }
@@ -2,35 +2,123 @@ use std::marker::PhantomData;
 
 use bevy_ecs::{
 	component::Component,
-	entity::{Entity, EntityHashSet},
-	system::{Command, Commands, EntityCommands},
-	world::World,
+	entity::{Entity, EntityHashSet, EntityIndexMap, EntityIndexSet},
+	resource::Resource,
+	system::{Command, EntityCommands, SystemBuffer, SystemMeta},
+	world::{DeferredWorld, World},
 };
+use bevy_platform::collections::HashSet;
+
+use crate::*;
 
 pub trait Many2Many: Component + Default + Clone {
-	fn new_self(entity_set: EntityHashSet) -> Self;
-	fn entity_set(self) -> EntityHashSet;
+	type Storage: ManyStorage;
+
+	fn new_self(entity_set: Self::Storage) -> Self;
+	fn entity_set(self) -> Self::Storage;
+	fn entity_set_ref(&self) -> &Self::Storage;
+	/// Through [World::modify_component] only, the component is immutable.
+	fn entity_set_mut(&mut self) -> &mut Self::Storage;
+	/// Checked on the moder side, the first struct of [relation_many](crate::relation_many).
+	fn constraints() -> ModConstraints {
+		ModConstraints::NONE
+	}
+}
+
+/// Entity set of one side, [EntityIndexSet] keep insertion order.
+pub trait ManyStorage: Default + Clone + IntoIterator<Item = Entity> + Send + Sync + 'static {
+	/// `false` if already exist.
+	fn insert_entity(&mut self, entity: Entity) -> bool;
+	/// `false` if not exist.
+	fn remove_entity(&mut self, entity: &Entity) -> bool;
+	fn contains_entity(&self, entity: &Entity) -> bool;
+	fn iter_entities(&self) -> impl Iterator<Item = Entity> + '_;
+	fn len(&self) -> usize;
+	fn is_empty(&self) -> bool;
+}
+
+impl ManyStorage for EntityHashSet {
+	fn insert_entity(&mut self, entity: Entity) -> bool {
+		self.insert(entity)
+	}
+	fn remove_entity(&mut self, entity: &Entity) -> bool {
+		self.remove(entity)
+	}
+	fn contains_entity(&self, entity: &Entity) -> bool {
+		self.contains(entity)
+	}
+	fn iter_entities(&self) -> impl Iterator<Item = Entity> + '_ {
+		self.iter().copied()
+	}
+	fn len(&self) -> usize {
+		EntityHashSet::len(self)
+	}
+	fn is_empty(&self) -> bool {
+		EntityHashSet::is_empty(self)
+	}
 }
 
+impl ManyStorage for EntityIndexSet {
+	fn insert_entity(&mut self, entity: Entity) -> bool {
+		self.insert(entity)
+	}
+	fn remove_entity(&mut self, entity: &Entity) -> bool {
+		self.shift_remove(entity)
+	}
+	fn contains_entity(&self, entity: &Entity) -> bool {
+		self.contains(entity)
+	}
+	fn iter_entities(&self) -> impl Iterator<Item = Entity> + '_ {
+		self.iter().copied()
+	}
+	fn len(&self) -> usize {
+		(**self).len()
+	}
+	fn is_empty(&self) -> bool {
+		(**self).is_empty()
+	}
+}
+
+/// Queued [WorldM2N](crate::WorldM2N), this entity hold `T` like [ModSide::change_now].
+///
+/// Bevy apply and flush every queued command alone, so each call is its own update.
+/// Many links of one system merged with `Deferred<ModBatch<T, U>>` or the bulk methods.
 pub trait EntityCommandM2N {
-	fn add_mod<T: ModExtent>(&mut self, child: Entity);
-	fn remove_mod<T: ModExtent>(&mut self, child: Entity);
+	fn add_mod<T: ModSide>(&mut self, child: Entity);
+	fn remove_mod<T: ModSide>(&mut self, child: Entity);
+	fn add_mods<T: ModSide>(&mut self, children: impl IntoIterator<Item = Entity>);
+	fn remove_mods<T: ModSide>(&mut self, children: impl IntoIterator<Item = Entity>);
+	fn clear_mods<T: ModSide>(&mut self);
+	/// Link exactly the children, kept link keep their position.
+	fn replace_mods<T: ModSide>(&mut self, children: impl IntoIterator<Item = Entity>);
 }
 
 impl<'a> EntityCommandM2N for EntityCommands<'a> {
-	fn add_mod<T: ModExtent>(&mut self, child: Entity) {
-		let ent = self.id();
-		T::add_it(self.commands_mut(), ent, child);
+	fn add_mod<T: ModSide>(&mut self, child: Entity) {
+		queue_change::<T>(self, ModsChange::Add(vec![child]));
+	}
+	fn remove_mod<T: ModSide>(&mut self, child: Entity) {
+		queue_change::<T>(self, ModsChange::Remove(vec![child]));
+	}
+	fn add_mods<T: ModSide>(&mut self, children: impl IntoIterator<Item = Entity>) {
+		queue_change::<T>(self, ModsChange::Add(children.into_iter().collect()));
+	}
+	fn remove_mods<T: ModSide>(&mut self, children: impl IntoIterator<Item = Entity>) {
+		queue_change::<T>(self, ModsChange::Remove(children.into_iter().collect()));
 	}
-	fn remove_mod<T: ModExtent>(&mut self, child: Entity) {
-		let ent = self.id();
-		T::remove_it(self.commands_mut(), ent, child);
+	fn clear_mods<T: ModSide>(&mut self) {
+		queue_change::<T>(self, ModsChange::Clear);
+	}
+	fn replace_mods<T: ModSide>(&mut self, children: impl IntoIterator<Item = Entity>) {
+		queue_change::<T>(self, ModsChange::Replace(children.into_iter().collect()));
 	}
 }
 
-pub trait ModExtent {
-	fn add_it(cmd: &mut Commands, ent_1: Entity, ent_2: Entity);
-	fn remove_it(cmd: &mut Commands, ent_1: Entity, ent_2: Entity);
+fn queue_change<T: ModSide>(ent_cmd: &mut EntityCommands, change: ModsChange) {
+	let owner = ent_cmd.id();
+	ent_cmd
+		.commands_mut()
+		.queue(move |world: &mut World| T::change_now(world, owner, change));
 }
 
 pub struct ShareMod<T: Many2Many, U: Many2Many> {
@@ -43,8 +131,8 @@ pub struct ShareMod<T: Many2Many, U: Many2Many> {
 impl<T: Many2Many, U: Many2Many> ShareMod<T, U> {
 	pub fn new(moder: Entity, entity: Entity) -> Self {
 		Self {
-			type_moder: PhantomData::default(),
-			type_entity: PhantomData::default(),
+			type_moder: PhantomData,
+			type_entity: PhantomData,
 			moder,
 			entity,
 		}
@@ -53,21 +141,7 @@ impl<T: Many2Many, U: Many2Many> ShareMod<T, U> {
 
 impl<T: Many2Many, U: Many2Many> Command for ShareMod<T, U> {
 	fn apply(self, world: &mut World) {
-		{
-			let mut mod_notif = world.get::<T>(self.moder).cloned().unwrap_or_default().entity_set();
-
-			if mod_notif.insert(self.entity) {
-				world.commands().entity(self.moder).insert(T::new_self(mod_notif));
-			}
-		}
-
-		{
-			let mut get_notif = world.get::<U>(self.entity).cloned().unwrap_or_default().entity_set();
-
-			if get_notif.insert(self.moder) {
-				world.commands().entity(self.entity).insert(U::new_self(get_notif));
-			}
-		}
+		ModBatch::<T, U>::new().share(self.moder, self.entity).apply(world);
 	}
 }
 
@@ -76,45 +150,339 @@ pub struct RemoveMod<T: Many2Many, U: Many2Many> {
 	type_entity: PhantomData<U>,
 	moder: Entity,
 	entity: Entity,
+	reason: UnlinkReason,
 }
 
 impl<T: Many2Many, U: Many2Many> RemoveMod<T, U> {
 	pub fn new(moder: Entity, entity: Entity) -> Self {
 		Self {
-			type_moder: PhantomData::default(),
-			type_entity: PhantomData::default(),
+			type_moder: PhantomData,
+			type_entity: PhantomData,
 			moder,
 			entity,
+			reason: UnlinkReason::Explicit,
 		}
 	}
+
+	pub fn with_reason(mut self, reason: UnlinkReason) -> Self {
+		self.reason = reason;
+		self
+	}
 }
 
 impl<T: Many2Many, U: Many2Many> Command for RemoveMod<T, U> {
 	fn apply(self, world: &mut World) {
-		if let Some(mut mod_notif) = world.get::<T>(self.moder).cloned().map(|v| v.entity_set()) {
-			let is_exist = mod_notif.remove(&self.entity);
-			if let Ok(mut ent_cmd) = world.commands().get_entity(self.moder)
-				&& is_exist
-			{
-				if mod_notif.is_empty() {
-					ent_cmd.remove::<T>();
-				} else {
-					ent_cmd.insert(T::new_self(mod_notif));
-				}
+		ModBatch::<T, U>::new()
+			.with_reason(self.reason)
+			.remove(self.moder, self.entity)
+			.apply(world);
+	}
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ModOp {
+	Share,
+	Remove,
+}
+
+/// Many [ShareMod] and [RemoveMod] as one command, every entity updated once by the net change.
+///
+/// Applied in push order, share then remove of the same pair is nothing.
+/// [Linked] and [Unlinked] triggered after every entity updated.
+/// With [ModConstraints], applied op by op so every share see the links before it.
+///
+/// As `Deferred<ModBatch<T, U>>` system param, every op pushed by the system applied once at its sync point.
+pub struct ModBatch<T: Many2Many, U: Many2Many> {
+	type_moder: PhantomData<T>,
+	type_entity: PhantomData<U>,
+	ops: Vec<(ModOp, Entity, Entity)>,
+	reason: UnlinkReason,
+}
+
+impl<T: Many2Many, U: Many2Many> Default for ModBatch<T, U> {
+	fn default() -> Self {
+		Self {
+			type_moder: PhantomData,
+			type_entity: PhantomData,
+			ops: Vec::new(),
+			reason: UnlinkReason::Explicit,
+		}
+	}
+}
+
+impl<T: Many2Many, U: Many2Many> ModBatch<T, U> {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	pub fn with_reason(mut self, reason: UnlinkReason) -> Self {
+		self.reason = reason;
+		self
+	}
+
+	pub fn share(mut self, moder: Entity, entity: Entity) -> Self {
+		self.push(ModOp::Share, moder, entity);
+		self
+	}
+
+	pub fn remove(mut self, moder: Entity, entity: Entity) -> Self {
+		self.push(ModOp::Remove, moder, entity);
+		self
+	}
+
+	pub fn push(&mut self, op: ModOp, moder: Entity, entity: Entity) {
+		self.ops.push((op, moder, entity));
+	}
+
+	pub fn len(&self) -> usize {
+		self.ops.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.ops.is_empty()
+	}
+}
+
+impl<T: Many2Many, U: Many2Many> Command for ModBatch<T, U> {
+	fn apply(self, world: &mut World) {
+		if T::constraints().is_none() {
+			self.apply_net(world);
+			return;
+		}
+
+		let reason = self.reason;
+		for (op, moder, entity) in self.ops {
+			if op == ModOp::Share && !admit::<T, U>(world, moder, entity) {
+				continue;
+			}
+			let mut batch = Self::new().with_reason(reason);
+			batch.push(op, moder, entity);
+			batch.apply_net(world);
+		}
+	}
+}
+
+impl<T: Many2Many, U: Many2Many> SystemBuffer for ModBatch<T, U> {
+	fn apply(&mut self, _: &SystemMeta, world: &mut World) {
+		let reason = self.reason;
+		Command::apply(std::mem::replace(self, Self::new().with_reason(reason)), world);
+	}
+
+	fn queue(&mut self, _: &SystemMeta, mut world: DeferredWorld) {
+		let reason = self.reason;
+		world
+			.commands()
+			.queue(std::mem::replace(self, Self::new().with_reason(reason)));
+	}
+}
+
+impl<T: Many2Many, U: Many2Many> ModBatch<T, U> {
+	/// Constraints not checked.
+	pub(crate) fn apply_net(self, world: &mut World) {
+		let mut moders = EntityIndexMap::<Vec<(ModOp, Entity)>>::default();
+		let mut entities = EntityIndexMap::<Vec<(ModOp, Entity)>>::default();
+		for (op, moder, entity) in self.ops {
+			if op == ModOp::Share && (world.get_entity(moder).is_err() || world.get_entity(entity).is_err()) {
+				continue;
+			}
+			moders.entry(moder).or_default().push((op, entity));
+			entities.entry(entity).or_default().push((op, moder));
+		}
+
+		// Moder side first, entity side only add pair the moder side miss, such as despawned moder
+		let mut changes = Vec::new();
+		for (moder, ops) in moders {
+			let net = update_side::<T>(world, moder, &ops);
+			changes.extend(net.into_iter().map(|(entity, linked)| (moder, entity, linked)));
+		}
+		let mut changed = changes
+			.iter()
+			.map(|(moder, entity, _)| (*moder, *entity))
+			.collect::<HashSet<_>>();
+		for (entity, ops) in entities {
+			let net = update_side::<U>(world, entity, &ops);
+			changes.extend(
+				net.into_iter()
+					.filter(|(moder, _)| changed.insert((*moder, entity)))
+					.map(|(moder, linked)| (moder, entity, linked)),
+			);
+		}
+
+		for (moder, entity, linked) in changes {
+			if linked {
+				trigger_linked::<T, U>(world, moder, entity);
+			} else {
+				trigger_unlinked::<T, U>(world, moder, entity, self.reason);
+			}
+		}
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ModsChange {
+	Add(Vec<Entity>),
+	Remove(Vec<Entity>),
+	Clear,
+	Replace(Vec<Entity>),
+}
+
+/// Bulk [ModsChange] of every link on `owner`, applied as one [ModBatch].
+pub struct ChangeMods<T: Many2Many, U: Many2Many> {
+	type_moder: PhantomData<T>,
+	type_entity: PhantomData<U>,
+	owner: Entity,
+	is_moder: bool,
+	change: ModsChange,
+	reason: UnlinkReason,
+}
+
+impl<T: Many2Many, U: Many2Many> ChangeMods<T, U> {
+	/// `owner` hold `T`.
+	pub fn moder(owner: Entity, change: ModsChange) -> Self {
+		Self {
+			type_moder: PhantomData,
+			type_entity: PhantomData,
+			owner,
+			is_moder: true,
+			change,
+			reason: UnlinkReason::Explicit,
+		}
+	}
+
+	/// `owner` hold `U`.
+	pub fn entity(owner: Entity, change: ModsChange) -> Self {
+		Self {
+			is_moder: false,
+			..Self::moder(owner, change)
+		}
+	}
+
+	pub fn with_reason(mut self, reason: UnlinkReason) -> Self {
+		self.reason = reason;
+		self
+	}
+}
+
+impl<T: Many2Many, U: Many2Many> Command for ChangeMods<T, U> {
+	fn apply(self, world: &mut World) {
+		let owner = self.owner;
+		// Only read for clear and replace, cloning the set on every add is quadratic
+		let current = || -> Vec<Entity> {
+			match self.is_moder {
+				true => world
+					.get::<T>(owner)
+					.map(|notif| notif.entity_set_ref().iter_entities().collect()),
+				false => world
+					.get::<U>(owner)
+					.map(|notif| notif.entity_set_ref().iter_entities().collect()),
+			}
+			.unwrap_or_default()
+		};
+
+		let mut batch = ModBatch::<T, U>::new().with_reason(self.reason);
+		let mut push = |op, child| match self.is_moder {
+			true => batch.push(op, owner, child),
+			false => batch.push(op, child, owner),
+		};
+		match self.change {
+			ModsChange::Add(children) => children.into_iter().for_each(|child| push(ModOp::Share, child)),
+			ModsChange::Remove(children) => children.into_iter().for_each(|child| push(ModOp::Remove, child)),
+			ModsChange::Clear => current().into_iter().for_each(|child| push(ModOp::Remove, child)),
+			ModsChange::Replace(children) => {
+				let keep = children.iter().copied().collect::<EntityHashSet>();
+				current()
+					.into_iter()
+					.filter(|child| !keep.contains(child))
+					.for_each(|child| push(ModOp::Remove, child));
+				children.into_iter().for_each(|child| push(ModOp::Share, child));
 			}
 		}
+		batch.apply(world);
+	}
+}
+
+/// Net change of `ops` on one side in place, [Replace](bevy_ecs::lifecycle::Replace) and
+/// [Insert](bevy_ecs::lifecycle::Insert) still triggered once.
+///
+/// Component added when first linked and removed when empty. Return the net change.
+pub(crate) fn update_side<T: Many2Many>(
+	world: &mut World,
+	owner: Entity,
+	ops: &[(ModOp, Entity)],
+) -> EntityIndexMap<bool> {
+	let Ok(ent_ref) = world.get_entity(owner) else {
+		return EntityIndexMap::default();
+	};
+	let current = ent_ref.get::<T>().map(|notif| notif.entity_set_ref());
+
+	// Linked after, in first change order
+	let mut net = EntityIndexMap::<bool>::default();
+	for (op, other) in ops {
+		net.insert(*other, *op == ModOp::Share);
+	}
+	net.retain(|other, linked| current.is_some_and(|set| set.contains_entity(other)) != *linked);
+	if net.is_empty() {
+		return net;
+	}
 
-		if let Some(mut get_notif) = world.get::<U>(self.entity).cloned().map(|v| v.entity_set()) {
-			let is_exist = get_notif.remove(&self.moder);
-			if let Ok(mut ent_cmd) = world.commands().get_entity(self.entity)
-				&& is_exist
-			{
-				if get_notif.is_empty() {
-					ent_cmd.remove::<U>();
+	let has_notif = current.is_some();
+	let remain = current.map_or(0, |set| set.len()) + net.values().filter(|linked| **linked).count()
+		- net.values().filter(|linked| !**linked).count();
+
+	if remain == 0 {
+		// The other side is updated by the caller
+		world.get_resource_or_init::<SideRemoving>().0 = Some(owner);
+		world.entity_mut(owner).remove::<T>();
+		world.resource_mut::<SideRemoving>().0 = None;
+	} else if has_notif {
+		let _ = world.modify_component::<T, _>(owner, |notif| {
+			let set = notif.entity_set_mut();
+			for (other, linked) in &net {
+				if *linked {
+					set.insert_entity(*other);
 				} else {
-					ent_cmd.insert(U::new_self(get_notif));
+					set.remove_entity(other);
 				}
 			}
-		}
+		});
+	} else {
+		let mut set = T::Storage::default();
+		net.keys().for_each(|other| {
+			set.insert_entity(*other);
+		});
+		world.entity_mut(owner).insert(T::new_self(set));
+	}
+	net
+}
+
+/// Owner [update_side] is removing the emptied component of, skipped by the hooks.
+#[derive(Resource, Default)]
+struct SideRemoving(Option<Entity>);
+
+/// `on_remove` and `on_despawn` hook of the moder side, unlink every linked entity.
+pub fn unlink_moder<T: Many2Many, U: Many2Many>(mut world: DeferredWorld, moder: Entity, reason: UnlinkReason) {
+	if world
+		.get_resource::<SideRemoving>()
+		.is_some_and(|removing| removing.0 == Some(moder))
+	{
+		return;
+	}
+	let mod_notif = world.entity(moder).get::<T>().cloned().unwrap().entity_set();
+	world.commands().queue(
+		ChangeMods::<T, U>::moder(moder, ModsChange::Remove(mod_notif.into_iter().collect())).with_reason(reason),
+	);
+}
+
+/// [unlink_moder] of the entity side.
+pub fn unlink_entity<T: Many2Many, U: Many2Many>(mut world: DeferredWorld, entity: Entity, reason: UnlinkReason) {
+	if world
+		.get_resource::<SideRemoving>()
+		.is_some_and(|removing| removing.0 == Some(entity))
+	{
+		return;
 	}
+	let get_notif = world.entity(entity).get::<U>().cloned().unwrap().entity_set();
+	world.commands().queue(
+		ChangeMods::<T, U>::entity(entity, ModsChange::Remove(get_notif.into_iter().collect())).with_reason(reason),
+	);
 }
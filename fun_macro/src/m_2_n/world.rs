@@ -0,0 +1,62 @@
+use bevy_ecs::{
+	entity::Entity,
+	world::{EntityWorldMut, World},
+};
+
+use crate::*;
+
+/// One side of a [relation_many](crate::relation_many) pair.
+pub trait ModSide: Many2Many {
+	type Other: ModSide<Other = Self>;
+
+	/// [ChangeMods] of `owner`, which hold `Self`, both side updated before return.
+	fn change_now(world: &mut World, owner: Entity, change: ModsChange);
+}
+
+/// Link right away, no command to flush.
+pub trait WorldM2N {
+	/// `owner` hold `T` with `other`, `other` hold the other side with `owner`.
+	fn link<T: ModSide>(&mut self, owner: Entity, other: Entity);
+	fn unlink<T: ModSide>(&mut self, owner: Entity, other: Entity);
+	/// Empty if `owner` not exist or not linked.
+	fn linked<T: Many2Many>(&self, owner: Entity) -> impl Iterator<Item = Entity> + '_;
+}
+
+impl WorldM2N for World {
+	fn link<T: ModSide>(&mut self, owner: Entity, other: Entity) {
+		T::change_now(self, owner, ModsChange::Add(vec![other]));
+	}
+	fn unlink<T: ModSide>(&mut self, owner: Entity, other: Entity) {
+		T::change_now(self, owner, ModsChange::Remove(vec![other]));
+	}
+	fn linked<T: Many2Many>(&self, owner: Entity) -> impl Iterator<Item = Entity> + '_ {
+		self.get::<T>(owner)
+			.into_iter()
+			.flat_map(|notif| notif.entity_set_ref().iter_entities())
+	}
+}
+
+/// [WorldM2N] of this entity.
+pub trait EntityWorldM2N {
+	fn link<T: ModSide>(&mut self, other: Entity) -> &mut Self;
+	fn unlink<T: ModSide>(&mut self, other: Entity) -> &mut Self;
+	fn linked<T: Many2Many>(&self) -> impl Iterator<Item = Entity> + '_;
+}
+
+impl<'w> EntityWorldM2N for EntityWorldMut<'w> {
+	fn link<T: ModSide>(&mut self, other: Entity) -> &mut Self {
+		let owner = self.id();
+		self.world_scope(|world| world.link::<T>(owner, other));
+		self
+	}
+	fn unlink<T: ModSide>(&mut self, other: Entity) -> &mut Self {
+		let owner = self.id();
+		self.world_scope(|world| world.unlink::<T>(owner, other));
+		self
+	}
+	fn linked<T: Many2Many>(&self) -> impl Iterator<Item = Entity> + '_ {
+		self.get::<T>()
+			.into_iter()
+			.flat_map(|notif| notif.entity_set_ref().iter_entities())
+	}
+}